@@ -18,66 +18,82 @@ fn App() -> Element {
         DragArea {
             active: true,
             Cell {
-                span: 1..2,
+                placement: GridPlacement::columns(1, 2),
                 rows: 1,
                 columns: 1,
                 DragTarget{
+                    id: "target-1".to_string(),
                     Draggable {
+                        id: "panel-1".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 1".to_string(),
                     }
                 }
             }
             Cell {
-                span: 3..1,
+                placement: GridPlacement::columns(3, 1),
                 rows: 3,
                 columns: 1,
                 DragTarget{
+                    id: "target-2".to_string(),
                     Draggable {
+                        id: "panel-2".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 2".to_string(),
                     }
                 }
                 DragTarget{
+                    id: "target-3".to_string(),
                     Draggable {
+                        id: "panel-3".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 3".to_string(),
                     }
                 }
                 DragTarget{
+                    id: "target-4".to_string(),
                     Draggable {
+                        id: "panel-4".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 4".to_string(),
                     }
                 }
             }
             Cell {
-                span: 4..2,
+                placement: GridPlacement::columns(4, 2),
                 rows: 3,
                 columns: 1,
                 DragTarget{
+                    id: "target-5".to_string(),
                     Draggable {
+                        id: "panel-5".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 5".to_string(),
                     }
                 }
                 DragTarget{
+                    id: "target-6".to_string(),
                     Draggable {
+                        id: "panel-6".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 6".to_string(),
                     }
                 }
                 DragTarget{
+                    id: "target-7".to_string(),
                     Draggable {
+                        id: "panel-7".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 7".to_string(),
                     }
                 }
             }
             Cell {
-                span: 6..3,
+                placement: GridPlacement::columns(6, 3),
                 DragTarget{
+                    id: "target-8".to_string(),
                     Draggable {
+                        id: "panel-8".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 8".to_string(),
                     }
@@ -0,0 +1,87 @@
+#![allow(non_snake_case)]
+
+use dioxus::prelude::*;
+use tracing::Level;
+
+use dx_magnet::components::*;
+
+fn main() {
+    // Init logger
+    dioxus_logger::init(Level::INFO).expect("failed to init logger");
+    dioxus::launch(App);
+}
+
+#[component]
+fn App() -> Element {
+    let mut landed = use_signal(|| "drag a panel into the other area".to_string());
+    let on_drop = move |event: PanelDragEvent| {
+        if let Some(destination) = event.destination {
+            landed.set(format!(
+                "{} landed in {} at {:?}",
+                event.panel_id,
+                destination.target_id.unwrap_or_default(),
+                destination.rect.origin
+            ));
+        }
+    };
+    rsx! {
+        link { rel: "stylesheet", href: "styles.css" }
+        p { "{landed}" }
+        DragCoordinator {
+            div {
+                style: "display: flex; flex-direction: column; gap: 2rem;",
+                DragArea {
+                    id: "left".to_string(),
+                    active: true,
+                    columns: 2,
+                    on_drop: on_drop,
+                    Cell {
+                        placement: GridPlacement::columns(1, 1),
+                        DragTarget {
+                            id: "left-1".to_string(),
+                            Draggable {
+                                id: "panel-1".to_string(),
+                                variant: DraggableVariants::DOCKED,
+                                title: "panel 1".to_string(),
+                            }
+                        }
+                    }
+                    Cell {
+                        placement: GridPlacement::columns(2, 1),
+                        DragTarget {
+                            id: "left-2".to_string(),
+                            Draggable {
+                                id: "panel-2".to_string(),
+                                variant: DraggableVariants::DOCKED,
+                                title: "panel 2".to_string(),
+                            }
+                        }
+                    }
+                }
+                DragArea {
+                    id: "right".to_string(),
+                    active: true,
+                    columns: 2,
+                    on_drop: on_drop,
+                    Cell {
+                        placement: GridPlacement::columns(1, 1),
+                        DragTarget {
+                            id: "right-1".to_string(),
+                            Draggable {
+                                id: "panel-3".to_string(),
+                                variant: DraggableVariants::DOCKED,
+                                title: "panel 3".to_string(),
+                            }
+                        }
+                    }
+                    Cell {
+                        placement: GridPlacement::columns(2, 1),
+                        DragTarget {
+                            id: "right-2".to_string(),
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -18,23 +18,25 @@ fn App() -> Element {
         DragArea {
             active: true,
             // Cell {
-            //     span: 1..2,
+            //     placement: GridPlacement::columns(1, 2),
             //     rows: 1,
             //     columns: 1,
             // }
             Cell {
-                span: 3..2,
+                placement: GridPlacement::columns(3, 2),
                 rows: 1,
                 columns: 1,
                 DragTarget {
+                    id: "target-1".to_string(),
                     Draggable {
+                        id: "panel-1".to_string(),
                         variant: DraggableVariants::DOCKED,
                         title: "panel 1".to_string(),
                     }
                 }
             }
             Cell {
-                span: 3..2
+                placement: GridPlacement::columns(3, 2)
             }
         }
     }
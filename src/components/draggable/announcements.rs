@@ -0,0 +1,48 @@
+use super::{DragEventKind, LayoutState, PanelDragEvent};
+
+/// Visually hides the live region while keeping it readable by screen readers.
+pub(crate) const LIVE_REGION_STYLES: &str = "
+    position: absolute;
+    width: 1px;
+    height: 1px;
+    margin: -1px;
+    padding: 0;
+    border: 0;
+    overflow: hidden;
+    clip: rect(0 0 0 0);
+    white-space: nowrap;
+";
+
+pub(crate) fn announce(
+    kind: DragEventKind,
+    event: &PanelDragEvent,
+    layout: &LayoutState,
+) -> Option<String> {
+    let panel = layout.get_panel_title(&event.panel_id);
+    match kind {
+        DragEventKind::DragStart => Some(format!("Picked up {}", panel)),
+        DragEventKind::TargetEnter => {
+            let target_id = event.destination.as_ref()?.target_id.clone()?;
+            let target = layout.get_target_label(&target_id);
+            let displaced: Vec<String> = layout
+                .get_panels_in_target(&target_id)
+                .iter()
+                .filter(|panel_id| **panel_id != event.panel_id)
+                .map(|panel_id| layout.get_panel_title(panel_id))
+                .collect();
+            match displaced.is_empty() {
+                true => Some(format!("Over {}", target)),
+                false => Some(format!(
+                    "Over {}, will swap with {}",
+                    target,
+                    displaced.join(" and ")
+                )),
+            }
+        }
+        DragEventKind::Drop => Some("Dropped".to_string()),
+        DragEventKind::Release => Some(format!("Released {}", panel)),
+        DragEventKind::Cancel => Some(format!("Cancelled, {} returned", panel)),
+        DragEventKind::Swap => Some(format!("{} moved", panel)),
+        DragEventKind::DragMove | DragEventKind::TargetLeave => None,
+    }
+}
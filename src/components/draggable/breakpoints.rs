@@ -0,0 +1,125 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A layout a DragArea switches to once it is at least `min_width` pixels
+/// wide, or while its `media` query matches.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Breakpoint {
+    pub name: String,
+    pub min_width: f64,
+    /// Decides the breakpoint instead of the area's width.
+    pub media: Option<String>,
+    pub columns: u8,
+    /// Targets panels move to when the breakpoint is first entered, keyed by Draggable id.
+    pub panels: BTreeMap<String, String>,
+}
+
+impl Breakpoint {
+    pub fn new(name: String, min_width: f64, columns: u8) -> Self {
+        Self {
+            name,
+            min_width,
+            media: None,
+            columns,
+            panels: BTreeMap::new(),
+        }
+    }
+
+    pub fn media(name: String, query: String, columns: u8) -> Self {
+        Self {
+            media: Some(query),
+            ..Self::new(name, 0., columns)
+        }
+    }
+
+    pub fn with_panel(mut self, panel_id: String, target_id: String) -> Self {
+        self.panels.insert(panel_id, target_id);
+        self
+    }
+
+    /// The media queries of the bundled styles.css, which shows 2, 5 and 8
+    /// columns from a 0, 35rem and 65rem wide viewport.
+    pub fn display_breakpoints() -> Vec<Self> {
+        vec![
+            Self::new("narrow".to_string(), 0., 2),
+            Self::media("medium".to_string(), "(min-width: 35rem)".to_string(), 5),
+            Self::media("wide".to_string(), "(min-width: 65rem)".to_string(), 8),
+        ]
+    }
+
+    /// Falls back to the narrowest breakpoint. Of breakpoints equally wide,
+    /// the last one listed wins.
+    pub fn select<'a>(
+        breakpoints: &'a [Self],
+        width: f64,
+        matching: &BTreeSet<String>,
+    ) -> Option<&'a Self> {
+        breakpoints
+            .iter()
+            .filter(|breakpoint| match &breakpoint.media {
+                Some(query) => matching.contains(query),
+                None => breakpoint.min_width <= width,
+            })
+            .max_by(|a, b| a.min_width.total_cmp(&b.min_width))
+            .or_else(|| {
+                breakpoints
+                    .iter()
+                    .min_by(|a, b| a.min_width.total_cmp(&b.min_width))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn select<'a>(breakpoints: &'a [Breakpoint], width: f64, matching: &[&str]) -> Option<&'a str> {
+        let matching = matching.iter().map(|query| query.to_string()).collect();
+        Breakpoint::select(breakpoints, width, &matching).map(|breakpoint| breakpoint.name.as_str())
+    }
+
+    #[test]
+    fn the_widest_fitting_breakpoint_is_selected() {
+        let breakpoints = [
+            Breakpoint::new("narrow".into(), 0., 2),
+            Breakpoint::new("wide".into(), 800., 8),
+            Breakpoint::new("medium".into(), 400., 5),
+        ];
+        assert_eq!(select(&breakpoints, 300., &[]), Some("narrow"));
+        assert_eq!(select(&breakpoints, 400., &[]), Some("medium"));
+        assert_eq!(select(&breakpoints, 1200., &[]), Some("wide"));
+    }
+
+    #[test]
+    fn equally_wide_breakpoints_select_the_last_listed() {
+        let breakpoints = [
+            Breakpoint::new("first".into(), 400., 4),
+            Breakpoint::new("second".into(), 400., 6),
+        ];
+        assert_eq!(select(&breakpoints, 500., &[]), Some("second"));
+        let breakpoints = Breakpoint::display_breakpoints();
+        let queries = ["(min-width: 35rem)", "(min-width: 65rem)"];
+        assert_eq!(select(&breakpoints, 0., &queries), Some("wide"));
+        assert_eq!(select(&breakpoints, 0., &queries[..1]), Some("medium"));
+    }
+
+    #[test]
+    fn media_breakpoints_ignore_the_width() {
+        let breakpoints = [
+            Breakpoint::new("narrow".into(), 0., 2),
+            Breakpoint::media("print".into(), "print".into(), 1),
+        ];
+        assert_eq!(select(&breakpoints, 2000., &[]), Some("narrow"));
+        assert_eq!(select(&breakpoints, 2000., &["print"]), Some("print"));
+    }
+
+    #[test]
+    fn too_narrow_areas_fall_back_to_the_narrowest() {
+        let breakpoints = [
+            Breakpoint::new("medium".into(), 400., 5),
+            Breakpoint::new("small".into(), 200., 3),
+            Breakpoint::new("also small".into(), 200., 2),
+        ];
+        assert_eq!(select(&breakpoints, 100., &[]), Some("small"));
+        assert_eq!(select(&[], 100., &[]), None);
+    }
+}
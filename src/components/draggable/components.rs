@@ -1,22 +1,265 @@
+use std::collections::{BTreeSet, HashMap};
 use std::rc::Rc;
 
+use crate::components::draggable::layout_store::sleep;
 use crate::components::layout::Container;
 use crate::components::{draggable::*, Window};
 use dioxus::prelude::*;
-use dioxus_elements::geometry::euclid::Rect;
+use dioxus_elements::geometry::{
+    euclid::{Point2D, Rect, Vector2D},
+    ClientSpace, ElementSpace,
+};
 use dx_flipbook::components::Animatable;
 use dx_flipbook::hooks::use_flipbook_signal;
 
 const DRAG_AREA_COLUMNS: u8 = 8u8;
-const DRAG_AREA_ROWS: u8 = 2u8;
+
+/// Scrolls the area, its scrollable ancestors and the window while the pointer
+/// is near their edges. Receives the pointer's client position, null once the
+/// drag ends, or true to stop.
+fn get_auto_scroll_script(area_id: &str) -> String {
+    format!(
+        r#"
+        const EDGE = 48;
+        const MAX_SPEED = 20;
+        const speed = (pos, start, end) => {{
+            if (pos < start + EDGE) return -MAX_SPEED * (1 - Math.max(pos - start, 0) / EDGE);
+            if (pos > end - EDGE) return MAX_SPEED * (1 - Math.max(end - pos, 0) / EDGE);
+            return 0;
+        }};
+        // the area is looked up per drag, as it is not mounted yet when this starts
+        const findScrollables = () => {{
+            const area = document.querySelector(`[data-drag-area="${{CSS.escape({:?})}}"]`);
+            const found = [];
+            for (let el = area; el; el = el.parentElement) {{
+                const style = getComputedStyle(el);
+                const scrolls = /(auto|scroll)/.test(style.overflow + style.overflowX + style.overflowY);
+                if (scrolls && (el.scrollHeight > el.clientHeight || el.scrollWidth > el.clientWidth)) {{
+                    found.push(el);
+                }}
+            }}
+            return found;
+        }};
+        let pointer = null;
+        // found once per drag, as reading every ancestor's style each frame is costly
+        let scrollables = [];
+        const step = () => {{
+            if (!pointer) return;
+            for (const el of scrollables) {{
+                const rect = el.getBoundingClientRect();
+                el.scrollBy(speed(pointer.x, rect.left, rect.right), speed(pointer.y, rect.top, rect.bottom));
+            }}
+            window.scrollBy(speed(pointer.x, 0, window.innerWidth), speed(pointer.y, 0, window.innerHeight));
+            requestAnimationFrame(step);
+        }};
+        while (true) {{
+            const message = await dioxus.recv();
+            if (message === true) {{
+                pointer = null;
+                break;
+            }}
+            const idle = !pointer;
+            pointer = message ? {{ x: message[0], y: message[1] }} : null;
+            if (pointer && idle) {{
+                scrollables = findScrollables();
+                requestAnimationFrame(step);
+            }}
+        }}
+        "#,
+        area_id
+    )
+}
+
+const BLUR_LISTENER_SCRIPT: &str = r#"
+    const onBlur = () => dioxus.send(true);
+    window.addEventListener("blur", onBlur);
+    await dioxus.recv();
+    window.removeEventListener("blur", onBlur);
+"#;
+
+/// Reports scrolling at most once a frame, until any message arrives.
+const SCROLL_LISTENER_SCRIPT: &str = r#"
+    let queued = false;
+    const onScroll = () => {
+        if (queued) return;
+        queued = true;
+        requestAnimationFrame(() => {
+            queued = false;
+            dioxus.send(true);
+        });
+    };
+    window.addEventListener("scroll", onScroll, true);
+    await dioxus.recv();
+    window.removeEventListener("scroll", onScroll, true);
+"#;
+
+/// Sends back the media queries it receives that match, whenever that changes.
+const MEDIA_LISTENER_SCRIPT: &str = r#"
+    const queries = await dioxus.recv();
+    const lists = queries.map((query) => window.matchMedia(query));
+    const onChange = () => dioxus.send(queries.filter((_, index) => lists[index].matches));
+    lists.forEach((list) => list.addEventListener("change", onChange));
+    onChange();
+    await dioxus.recv();
+    lists.forEach((list) => list.removeEventListener("change", onChange));
+"#;
 
 #[component]
-pub fn DragArea(active: bool, children: Element) -> Element {
-    let mut global_drag_info = use_context_provider(|| Signal::new(GlobalDragState::new()));
+pub fn DragArea(
+    id: Option<String>,
+    accepts_from: Option<Vec<String>>,
+    active: bool,
+    label: Option<String>,
+    long_press_ms: Option<u64>,
+    drag_distance: Option<f64>,
+    drag_delay_ms: Option<u64>,
+    columns: Option<u8>,
+    rows: Option<u8>,
+    row_size: Option<String>,
+    grid_areas: Option<Vec<String>>,
+    gap: Option<f64>,
+    padding: Option<f64>,
+    breakpoints: Option<Vec<Breakpoint>>,
+    layout: Option<LayoutSnapshot>,
+    on_layout_change: Option<EventHandler<LayoutSnapshot>>,
+    persistence: Option<LayoutPersistence>,
+    on_error: Option<EventHandler<DragError>>,
+    on_drag_start: Option<EventHandler<PanelDragEvent>>,
+    on_drag_move: Option<EventHandler<PanelDragEvent>>,
+    on_target_enter: Option<EventHandler<PanelDragEvent>>,
+    on_target_leave: Option<EventHandler<PanelDragEvent>>,
+    on_drop: Option<EventHandler<PanelDragEvent>>,
+    on_swap: Option<EventHandler<PanelDragEvent>>,
+    on_release: Option<EventHandler<PanelDragEvent>>,
+    on_cancel: Option<EventHandler<PanelDragEvent>>,
+    children: Element,
+) -> Element {
+    // an area nested in another area's panel keeps its own drag, layout and grid
+    let depth = try_use_context::<DragAreaConfig>().map_or(0, |config| config.depth + 1);
+    let enclosing_panel = try_use_context::<Signal<LocalDragState>>();
+    // areas inside a DragCoordinator share their drag and layout
+    let shared = try_use_context::<SharedDragState>().filter(|shared| shared.depth == depth);
+    let mut global_drag_info = use_context_provider(|| match shared {
+        Some(shared) => shared.global_drag_info,
+        None => Signal::new(GlobalDragState::new()),
+    });
+    let mut layout_state = use_context_provider(|| match shared {
+        Some(shared) => shared.layout_state,
+        None => Signal::new(LayoutState::new()),
+    });
+    let mut ids = use_context_provider(|| match shared {
+        Some(shared) => shared.ids,
+        None => CopyValue::new(IdRegistry::new()),
+    });
+    let (area_id, id_error) = use_hook(|| ids.write().claim_area_id(id.clone()));
+
+    use_context_provider(|| {
+        let mut config = DragAreaConfig::default();
+        config.area_id = area_id.clone();
+        config.depth = depth;
+        if let Some(delay) = long_press_ms {
+            config.long_press_delay = web_time::Duration::from_millis(delay);
+        }
+        if let Some(distance) = drag_distance {
+            config.drag_distance = distance;
+        }
+        config.drag_delay = drag_delay_ms.map(web_time::Duration::from_millis);
+        config
+    });
+    let panel_handlers =
+        use_context_provider(|| CopyValue::new(HashMap::<String, DragEventHandlers>::new()));
 
-    let mut grid =
-        use_context_provider(|| Signal::new(GridData::new(DRAG_AREA_COLUMNS, DRAG_AREA_ROWS)));
+    // Cell spans are declared against the widest breakpoint's columns
+    let declared_columns = columns
+        .or(breakpoints
+            .iter()
+            .flatten()
+            .map(|breakpoint| breakpoint.columns)
+            .max())
+        .unwrap_or(DRAG_AREA_COLUMNS);
+    let mut grid = use_context_provider(|| {
+        let mut grid = GridData::new(declared_columns.max(1), rows.map(|rows| rows.max(1)));
+        grid.set_row_size(row_size.clone());
+        grid.set_spacing(gap.unwrap_or_default(), padding.unwrap_or_default());
+        // an invalid template is reported by the effect below, which also runs on mount
+        let _ = grid.set_areas(grid_areas.clone().unwrap_or_default());
+        if let Some(sizes) = layout_state.peek().get_grid_sizes(&area_id) {
+            grid.set_sizes(sizes.clone());
+        }
+        Signal::new(grid)
+    });
+    // the area joins the layout once mounted, as the layout is shared with the
+    // areas already rendered
+    let joined_area = area_id.clone();
+    let joined_sources = accepts_from.clone();
+    let (initial_layout, initial_persistence) = (layout.clone(), persistence.clone());
+    use_effect(move || {
+        let area_id = joined_area.clone();
+        let mut layout_write = layout_state.write();
+        if let Some(error) = id_error.clone() {
+            layout_write.report_error(error);
+        }
+        layout_write.register_area(area_id.clone(), joined_sources.clone());
+        let claimed = initial_persistence.as_ref().is_some_and(|persistence| {
+            layout_write.claim_persistence(persistence.get_name().to_string(), area_id.clone())
+        });
+        // areas sharing a saved layout load it once, through the first of them
+        match (initial_layout.clone(), &initial_persistence) {
+            (Some(snapshot), _) => layout_write.import_area(&area_id, snapshot),
+            (None, Some(persistence)) if claimed => match persistence.load() {
+                Ok(Some(snapshot)) => layout_write.import_area(&area_id, snapshot),
+                Ok(None) => (),
+                Err(error) => layout_write.report_error(error),
+            },
+            _ => (),
+        }
+        if let Some(sizes) = layout_write.get_grid_sizes(&area_id) {
+            grid.write().set_sizes(sizes.clone());
+        }
+    });
+    // the grid follows later changes to its props
+    use_effect(use_reactive(
+        (
+            &declared_columns,
+            &rows,
+            &row_size,
+            &grid_areas,
+            &gap,
+            &padding,
+        ),
+        move |(columns, rows, row_size, grid_areas, gap, padding)| {
+            let mut next = grid.peek().clone();
+            next.set_declared_columns(columns);
+            next.set_rows(rows.map(|rows| rows.max(1)));
+            next.set_row_size(row_size);
+            next.set_spacing(gap.unwrap_or_default(), padding.unwrap_or_default());
+            if let Err(error) = next.set_areas(grid_areas.unwrap_or_default()) {
+                layout_state.write().report_error(error);
+            }
+            if next != *grid.peek() {
+                grid.set(next);
+            }
+        },
+    ));
+    // splitters resize the grid's tracks, which are saved with the layout
+    let sizes_area = area_id.clone();
+    // a splitter being dragged is recorded once, when it is released
+    let grid_sizes = use_memo(move || {
+        let grid = grid.read();
+        (!grid.is_resizing()).then(|| grid.get_sizes().clone())
+    });
+    use_effect(move || {
+        let Some(sizes) = grid_sizes() else {
+            return;
+        };
+        if layout_state.peek().get_grid_sizes(&sizes_area) != Some(&sizes) {
+            layout_state
+                .write()
+                .record_grid_sizes(sizes_area.clone(), sizes);
+        }
+    });
     let mut mounted = use_signal(|| None as Option<Rc<MountedData>>);
+    let origin_area = use_hook(|| CopyValue::new(area_id.clone()));
     let read_area_rect = move || async move {
         tracing::info!("reading area rect");
         let read = mounted.peek();
@@ -28,47 +271,418 @@ pub fn DragArea(active: bool, children: Element) -> Element {
                 tracing::info!("old area: {:?} vs new: {:?}", old_rect, rect);
                 if old_rect != Some(rect.cast_unit()) {
                     tracing::info!("setting area rect");
-                    grid.write().update_mounted(rect.cast_unit());
+                    let layout_width = mounted.peek().as_deref().and_then(get_layout_width);
+                    grid.write().update_mounted(rect.cast_unit(), layout_width);
+                    let area_id = origin_area.peek().clone();
+                    let origin = rect.origin.cast_unit();
+                    if layout_state.peek().get_area_origin(&area_id) != Some(origin) {
+                        layout_state.write().record_area_origin(area_id, origin);
+                    }
                 }
             }
         }
     };
-    // implement window size adjustments after dx 0.6
-    // let window_size = use_window_size();
-    // use_effect(move || {
-    //     let _window_size = window_size.read();
-    //     if let Some(rect) = grid.peek().get_grid_rect() {
-    //         spawn(async move {
-    //             read_area_rect().await;
-    //         });
-    //     }
-    // });
+    // areas sharing a layout measure everything from one area's corner
+    let frame_area = area_id.clone();
+    let frame_offset = use_memo(move || layout_state.read().get_frame_offset(&frame_area));
+    use_effect(move || {
+        let offset = frame_offset().map_or(Vector2D::zero(), |offset| {
+            (offset / grid.peek().get_scale()).cast_unit()
+        });
+        if grid.peek().get_frame_offset() != offset {
+            grid.write().set_frame_offset(offset);
+        }
+    });
+    // the window or the page's layout resizing the area moves every target
+    let area_resizes = use_resize_count(mounted);
     use_effect(move || {
         let _trig = mounted();
+        let _resizes = area_resizes.read();
         spawn(async move {
             read_area_rect().await;
         });
     });
+    // scrolling the page or any scrollable ancestor moves the area under the pointer
+    let scroll = use_hook(move || {
+        let mut scroll = eval(SCROLL_LISTENER_SCRIPT);
+        spawn(async move {
+            while scroll.recv().await.is_ok() {
+                read_area_rect().await;
+            }
+        });
+        scroll
+    });
+    use_drop(move || {
+        let _ = scroll.send(true.into());
+    });
+    use_context_provider(|| AreaElement(mounted));
+    // the enclosing panel carries a nested area, so re-read where it ended up
+    use_effect(move || {
+        if let Some(enclosing_panel) = enclosing_panel {
+            if enclosing_panel.read().get_placement().is_some() {
+                spawn(async move {
+                    read_area_rect().await;
+                });
+            }
+        }
+    });
+    // draggables outside a DragTarget must not see the enclosing area's target
+    use_context_provider(|| Signal::new(None as Option<SnapInfo>));
+
+    let dropped_area = area_id.clone();
+    use_drop(move || {
+        if let Ok(mut layout) = layout_state.try_write() {
+            layout.remove_area(&dropped_area);
+        }
+        if let Ok(mut ids) = ids.try_write() {
+            ids.release_area_id(&dropped_area);
+        }
+    });
+
+    let export_area = area_id.clone();
+    let snapshot = use_memo(move || layout_state.read().export_area(&export_area));
+    // an area that mounts empty has nothing to report, but one emptied later does
+    let mut reported = use_hook(|| CopyValue::new(false));
+    use_effect(move || {
+        let snapshot = snapshot();
+        if snapshot.is_empty() && !*reported.peek() {
+            return;
+        }
+        reported.set(true);
+        if let Some(handler) = on_layout_change {
+            handler.call(snapshot);
+        }
+    });
+
+    // autosave once a drop, an undo or a splitter comes to rest, and the
+    // layout has stayed put for the debounce duration. only the first area
+    // sharing a saved layout saves it, for all of them
+    let saved_area = area_id.clone();
+    let mut latest_persistence = use_hook(|| CopyValue::new(persistence.clone()));
+    let mut last_saved = use_hook(|| CopyValue::new(None as Option<LayoutSnapshot>));
+    if *latest_persistence.peek() != persistence {
+        latest_persistence.set(persistence.clone());
+        last_saved.set(None);
+    }
+    let is_dragging = use_memo(move || global_drag_info.read().is_dragging());
+    let moving = use_memo(move || {
+        is_dragging() || grid.read().is_resizing() || layout_state.read().has_pending_placements()
+    });
+    let settled = use_memo(move || layout_state.read().is_settled());
+    // None until the area first comes to rest, so the layout it mounts with isn't saved
+    let mut moved = use_hook(|| CopyValue::new(None as Option<bool>));
+    let mut save_generation = use_signal(|| 0u64);
+    use_effect(move || {
+        let (moving, settled) = (moving(), settled());
+        let landed = match *moved.peek() {
+            None => {
+                if !moving && settled {
+                    moved.set(Some(false));
+                }
+                false
+            }
+            Some(_) if moving => {
+                moved.set(Some(true));
+                false
+            }
+            Some(true) if settled => {
+                moved.set(Some(false));
+                true
+            }
+            _ => false,
+        };
+        if !landed {
+            return;
+        }
+        let generation = *save_generation.peek() + 1;
+        save_generation.set(generation);
+        let saved_area = saved_area.clone();
+        spawn(async move {
+            let debounce = match latest_persistence.peek().as_ref() {
+                Some(persistence) => persistence.get_debounce(),
+                None => return,
+            };
+            sleep(debounce).await;
+            if *save_generation.peek() != generation {
+                return;
+            }
+            let persistence = match latest_persistence.peek().clone() {
+                Some(persistence) => persistence,
+                None => return,
+            };
+            let snapshot = {
+                let layout = layout_state.peek();
+                let areas = layout.get_persistence_areas(persistence.get_name());
+                if areas.first() != Some(&saved_area) {
+                    return;
+                }
+                layout.export_areas(&areas)
+            };
+            if last_saved.peek().as_ref() == Some(&snapshot) {
+                return;
+            }
+            match persistence.save(&snapshot) {
+                Ok(()) => last_saved.set(Some(snapshot)),
+                Err(error) => layout_state.write().report_error(error),
+            }
+        });
+    });
+
+    // breakpoints with a media query follow the viewport rather than the area
+    let media_queries: Vec<String> = breakpoints
+        .iter()
+        .flatten()
+        .filter_map(|breakpoint| breakpoint.media.clone())
+        .collect();
+    let mut media_matches = use_signal(|| media_queries.is_empty().then(BTreeSet::new));
+    let media = use_hook(move || {
+        if media_queries.is_empty() {
+            return None;
+        }
+        let mut media = eval(MEDIA_LISTENER_SCRIPT);
+        let _ = media.send(media_queries.into());
+        spawn(async move {
+            while let Ok(matching) = media.recv().await {
+                let matching = matching
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|query| query.as_str().map(str::to_string))
+                    .collect();
+                media_matches.set(Some(matching));
+            }
+        });
+        Some(media)
+    });
+    use_drop(move || {
+        if let Some(media) = media {
+            let _ = media.send(true.into());
+        }
+    });
+
+    // lay the grid and its panels out for the breakpoint that fits the area's width
+    let breakpoint_area = area_id.clone();
+    let area_width = use_memo(move || grid.read().get_area_rect().map(|rect| rect.width()));
+    use_effect(move || {
+        let breakpoints = match &breakpoints {
+            Some(breakpoints) => breakpoints,
+            None => return,
+        };
+        // wait for the media queries, so the area doesn't flow into a breakpoint it leaves at once
+        let media_matches = media_matches.read();
+        let matching = match media_matches.as_ref() {
+            Some(matching) => matching,
+            None => return,
+        };
+        let breakpoint =
+            match area_width().and_then(|width| Breakpoint::select(breakpoints, width, matching)) {
+                Some(breakpoint) => breakpoint,
+                None => return,
+            };
+        if grid.peek().get_columns() != breakpoint.columns {
+            grid.write().set_columns(breakpoint.columns);
+        }
+        // panels re-flow once every panel is resting, so the arrangement left behind is complete
+        let settled = layout_state.read().is_settled() && !is_dragging();
+        if settled
+            && layout_state
+                .peek()
+                .get_active_breakpoint(&breakpoint_area)
+                .as_ref()
+                != Some(&breakpoint.name)
+        {
+            layout_state
+                .write()
+                .switch_breakpoint(&breakpoint_area, breakpoint);
+        }
+    });
+
+    use_effect(move || {
+        if !layout_state.read().has_errors() {
+            return;
+        }
+        for error in layout_state.write().take_errors() {
+            match on_error {
+                Some(handler) => handler.call(error),
+                None => tracing::error!("{}", error),
+            }
+        }
+    });
+
+    // merge split targets back once a side is vacated and every panel has come to rest
+    use_effect(move || {
+        let settled = layout_state.read().is_settled() && !is_dragging();
+        if settled && !layout_state.peek().get_vacated_splits().is_empty() {
+            layout_state.write().collapse_vacated_splits();
+        }
+    });
+
+    // scroll the area and its scrollable ancestors while a dragging pointer nears their edges
+    let mut pointer_client = use_signal(|| None as Option<Point2D<f64, ClientSpace>>);
+    let is_pointer_drag = use_memo(move || global_drag_info.read().is_pointer_drag());
+    let drag_pointer = use_memo(move || match is_pointer_drag() {
+        true => pointer_client(),
+        false => None,
+    });
+    let scroll_area = area_id.clone();
+    let auto_scroll = use_hook(move || eval(&get_auto_scroll_script(&scroll_area)));
+    use_effect(move || {
+        let pointer = drag_pointer().map_or(().into(), |pointer| vec![pointer.x, pointer.y].into());
+        if let Err(err) = auto_scroll.send(pointer) {
+            tracing::error!("could not update auto scroll: {:?}", err);
+        }
+    });
+    use_drop(move || {
+        let _ = auto_scroll.send(true.into());
+    });
+    // scrolling moves the area under a still pointer, so the grabbed panel and hit tests follow
+    let area_origin = use_memo(move || grid.read().get_grid_rect().map(|rect| rect.origin));
+    use_effect(move || {
+        let _origin = area_origin.read();
+        if let Some(pointer) = *drag_pointer.peek() {
+            let point = grid.peek().to_area_point(pointer);
+            global_drag_info.write().update_drag(point);
+        }
+    });
+    let area_attribute = area_id.clone();
+
+    // translate drag state changes into lifecycle callbacks
+    let area_handlers = DragEventHandlers {
+        on_drag_start,
+        on_drag_move,
+        on_target_enter,
+        on_target_leave,
+        on_drop,
+        on_swap,
+        on_release,
+        on_cancel,
+    };
+    let mut event_tracker =
+        use_signal(|| DragEventTracker::new(global_drag_info.peek().get_emitted_count()));
+    let mut announcement = use_signal(String::new);
+    use_effect(move || {
+        let global = global_drag_info.read();
+        let events = event_tracker.write().observe(
+            global.get_drag_state(),
+            global.get_snap_info(),
+            global.get_emitted(),
+            &layout_state.peek(),
+        );
+        drop(global);
+        // in a DragCoordinator every area sees every drag, so each reports its own
+        let layout = layout_state.peek();
+        let events: Vec<(DragEventKind, PanelDragEvent)> = events
+            .into_iter()
+            .filter(|(_, event)| layout.involves_area(&area_id, event))
+            .collect();
+        let announcements: Vec<String> = events
+            .iter()
+            .filter(|(_, event)| layout.get_panel_area(&event.panel_id).as_ref() == Some(&area_id))
+            .filter_map(|(kind, event)| announce(*kind, event, &layout))
+            .collect();
+        drop(layout);
+        if !announcements.is_empty() {
+            announcement.set(announcements.join(". "));
+        }
+        for (kind, event) in events {
+            let panel = panel_handlers.read().get(&event.panel_id).copied();
+            area_handlers.call(kind, event.clone());
+            if let Some(panel) = panel {
+                panel.call(kind, event);
+            }
+        }
+    });
 
     let mut on_pointer_move = move |event: PointerEvent| {
-        if !active || !global_drag_info.peek().is_dragging() {
+        if !active
+            || !global_drag_info
+                .peek()
+                .is_dragged_by_pointer(event.data.pointer_id())
+        {
             return;
         }
-        let point = event.data.client_coordinates();
-        global_drag_info.write().update_drag(point.cast_unit());
+        event.stop_propagation();
+        pointer_client.set(Some(event.data.client_coordinates()));
+        let point = grid.peek().to_area_point(event.data.client_coordinates());
+        global_drag_info.write().update_drag(point);
+    };
+    // losing window focus mid-drag would otherwise leave the drag stuck
+    let blur = use_hook(move || {
+        let mut blur = eval(BLUR_LISTENER_SCRIPT);
+        spawn(async move {
+            while blur.recv().await.is_ok() {
+                DraggableStateController::cancel_drag(global_drag_info);
+            }
+        });
+        blur
+    });
+    use_drop(move || {
+        let _ = blur.send(true.into());
+    });
+
+    // handled keys stop here so an enclosing area does not act on them too
+    let on_key_down = move |event: KeyboardEvent| {
+        if event.data.key() == Key::Escape {
+            if global_drag_info.peek().is_dragging() {
+                event.stop_propagation();
+                DraggableStateController::cancel_drag(global_drag_info);
+            }
+            return;
+        }
+        let modifiers = event.data.modifiers();
+        if !active || !(modifiers.ctrl() || modifiers.meta()) {
+            return;
+        }
+        let is_z = match event.data.key() {
+            Key::Character(character) => character.eq_ignore_ascii_case("z"),
+            _ => false,
+        };
+        if !is_z {
+            return;
+        }
+        event.stop_propagation();
+        match modifiers.shift() {
+            true => DraggableStateController::redo(global_drag_info, layout_state),
+            false => DraggableStateController::undo(global_drag_info, layout_state),
+        };
     };
     let style = use_memo(move || global_drag_info.read().get_drag_area_style());
+    let container_style = use_memo(move || Some(grid.read().get_grid_style()));
 
     rsx! {
         div {
             style: style,
             onpointermove: move |event| on_pointer_move(event),
-            onpointerup: move |_| DraggableStateController::stop_drag(global_drag_info),
+            tabindex: 0,
+            onpointerup: move |event| {
+                if global_drag_info.peek().is_dragged_by_pointer(event.data.pointer_id()) {
+                    event.stop_propagation();
+                    DraggableStateController::stop_drag(global_drag_info, layout_state);
+                }
+            },
+            onpointercancel: move |event| {
+                if global_drag_info.peek().is_dragged_by_pointer(event.data.pointer_id()) {
+                    event.stop_propagation();
+                    DraggableStateController::cancel_drag(global_drag_info);
+                }
+            },
+            onkeydown: on_key_down,
             onmounted: move |cx| mounted.set(Some(cx.data())),
+            "data-drag-area": "{area_attribute}",
+            role: "application",
+            aria_roledescription: "panel layout",
+            aria_label: label.unwrap_or("Panel layout".to_string()),
             Container {
-                columns: 8,
+                columns: grid.read().get_columns() as u64,
+                style: container_style(),
                 {children}
             }
+            div {
+                style: LIVE_REGION_STYLES,
+                aria_live: "assertive",
+                aria_atomic: "true",
+                "{announcement}"
+            }
         }
     }
 }
@@ -81,18 +695,77 @@ pub enum DraggableVariants {
 
 #[component]
 pub fn Draggable(
+    id: Option<String>,
     variant: DraggableVariants,
     title: String,
+    group: Option<String>,
+    tags: Option<Vec<String>>,
     style: Option<String>,
+    on_drag_start: Option<EventHandler<PanelDragEvent>>,
+    on_drag_move: Option<EventHandler<PanelDragEvent>>,
+    on_target_enter: Option<EventHandler<PanelDragEvent>>,
+    on_target_leave: Option<EventHandler<PanelDragEvent>>,
+    on_drop: Option<EventHandler<PanelDragEvent>>,
+    on_swap: Option<EventHandler<PanelDragEvent>>,
+    on_release: Option<EventHandler<PanelDragEvent>>,
+    on_cancel: Option<EventHandler<PanelDragEvent>>,
     children: Element,
     handle: Option<Element>,
 ) -> Element {
-    let id = use_signal(|| uuid::Uuid::new_v4().to_string());
+    let mut layout_state = use_context::<Signal<LayoutState>>();
+    let mut ids = use_context::<CopyValue<IdRegistry>>();
+    let (claimed_id, id_error) = use_hook(|| ids.write().claim_panel_id(id.clone()));
+    let id = use_signal(|| claimed_id);
+    let config = use_context::<DragAreaConfig>();
+    use_effect(move || {
+        let mut layout = layout_state.write();
+        if let Some(error) = id_error.clone() {
+            layout.report_error(error);
+        }
+        layout.record_panel_area(id.peek().clone(), config.area_id.clone());
+    });
+    // announcements name the panel by its current title
+    use_effect(use_reactive(&title, move |title| {
+        layout_state
+            .write()
+            .record_panel_title(id.peek().clone(), title);
+    }));
+    use_effect(use_reactive((&group, &tags), move |(group, tags)| {
+        layout_state.write().record_panel_traits(PanelTraits {
+            panel_id: id.peek().clone(),
+            group,
+            tags: tags.unwrap_or_default(),
+        });
+    }));
     let mut local_drag_info =
         use_context_provider(|| Signal::new(LocalDragState::new(variant, id())));
     let global_drag_info: Signal<GlobalDragState> = use_context::<Signal<GlobalDragState>>();
+    let mut panel_handlers = use_context::<CopyValue<HashMap<String, DragEventHandlers>>>();
+    use_hook(|| {
+        panel_handlers.write().insert(
+            id.peek().clone(),
+            DragEventHandlers {
+                on_drag_start,
+                on_drag_move,
+                on_target_enter,
+                on_target_leave,
+                on_drop,
+                on_swap,
+                on_release,
+                on_cancel,
+            },
+        );
+    });
     let mut animation_controller = use_flipbook_signal();
-    let current_rect = use_memo(move || animation_controller.read().read_rect());
+    // the animation library positions panels inside the area's Container, so
+    // rects are moved between area space and the Container at its boundary
+    let grid = use_context::<Signal<GridData>>();
+    let current_rect = use_memo(move || {
+        animation_controller
+            .read()
+            .read_rect()
+            .map(|rect| grid.read().from_container_rect(rect.cast_unit()))
+    });
     let animation_is_active = use_memo(move || !animation_controller.read().read_is_finished());
 
     let initial_snap_info = use_context::<Signal<Option<SnapInfo>>>();
@@ -100,19 +773,92 @@ pub fn Draggable(
     // should only write to local state once the targets are mounted
     use_effect(move || {
         if let Some(snap) = initial_snap_info() {
+            if let Some(target_id) = snap.target_id.clone() {
+                let panel_id = id.peek().clone();
+                if layout_state.peek().get_home_target(&panel_id).as_ref() != Some(&target_id) {
+                    layout_state.write().record_home_target(panel_id, target_id);
+                }
+            }
             if local_drag_info.peek().get_is_uninitialized() {
                 local_drag_info.write().initialize(snap);
             }
         }
     });
 
-    // implement resizing on window resize after dx 0.6 release
-    // use_window_size() currently does not cooperate with dx core unless on master
-    // let grid = use_context::<Signal<GridData>>();
-    // use_effect(move || {
-    //     let read = grid.read();
-    //     local_drag_info.write().resize_snapped(read.clone());
-    // });
+    // apply placements queued by a layout import
+    use_effect(move || {
+        let can_place = {
+            let local = local_drag_info.read();
+            local.get_is_uninitialized() || local.get_placement().is_some()
+        };
+        if !can_place {
+            return;
+        }
+        let panel_id = id.peek().clone();
+        let rest = match layout_state.read().resolve_pending(&panel_id) {
+            Some(rest) => rest,
+            None => return,
+        };
+        if local_drag_info.write().place(rest).is_ok() {
+            layout_state.write().clear_pending(&panel_id);
+        }
+    });
+
+    // report settled placements so the layout can be exported
+    use_effect(move || {
+        let panel_id = id.peek().clone();
+        let placement = match local_drag_info.read().get_placement() {
+            Some(placement) => placement,
+            None => {
+                if !layout_state.peek().is_unsettled(&panel_id) {
+                    layout_state.write().mark_unsettled(panel_id);
+                }
+                return;
+            }
+        };
+        if layout_state.peek().get_panel(&panel_id) != Some(&placement)
+            || layout_state.peek().is_unsettled(&panel_id)
+        {
+            layout_state.write().record_panel(panel_id, placement);
+        }
+    });
+
+    let dropped_id = id.peek().clone();
+    use_drop(move || {
+        let dragging_self = global_drag_info
+            .try_peek()
+            .is_ok_and(|global| global.get_dragged_panel().as_ref() == Some(&dropped_id));
+        if dragging_self {
+            DraggableStateController::cancel_drag(global_drag_info);
+        }
+        if let Ok(mut layout) = layout_state.try_write() {
+            layout.remove_panel(&dropped_id);
+        }
+        if let Ok(mut ids) = ids.try_write() {
+            ids.release_panel_id(&dropped_id);
+        }
+        if let Ok(mut handlers) = panel_handlers.try_write() {
+            handlers.remove(&dropped_id);
+        }
+    });
+
+    // follow the target when it is resized
+    let resting_target = use_memo(move || {
+        let target_id = match local_drag_info.read().get_placement()? {
+            PanelPlacement::Snapped {
+                target_id: Some(target_id),
+                ..
+            } => target_id,
+            _ => return None,
+        };
+        let rect = layout_state.read().get_target_rect(&target_id)?;
+        Some(SnapInfo::new(Some(target_id), rect))
+    });
+    use_effect(move || {
+        if let Some(snap_info) = resting_target() {
+            local_drag_info.write().resize_snapped(snap_info);
+        }
+    });
 
     use_effect(move || {
         if animation_is_active() {
@@ -127,7 +873,23 @@ pub fn Draggable(
             }
         };
         let global = global_drag_info.read().get_drag_state();
+        let releasing = matches!(global, DragAreaStates::Released(DragEndings::Releasing(_)));
+        let source = match local_drag_info.peek().get_drag_state() {
+            DraggableStates::Grabbed(grab_data) => Some(grab_data.drag_origin),
+            _ => None,
+        };
         local_drag_info.write().update_state(global, rect);
+        // only the panel knows where a release outside any target leaves it
+        let rest = local_drag_info.peek().get_drag_state();
+        if let (true, Some(source), DraggableStates::Resting(DraggableRestStates::Released(rest))) =
+            (releasing, source, rest)
+        {
+            let panel_id = local_drag_info.peek().get_element_id();
+            if let Some(operation) = LayoutOperation::from_release(panel_id, source, rest) {
+                let operation = operation.with_source_stack(&layout_state.peek());
+                global_drag_info.write().record_operation(operation);
+            }
+        }
     });
 
     let mut send_position_data = move |position_data: DraggablePositionData| {
@@ -136,25 +898,39 @@ pub fn Draggable(
         }
 
         match position_data {
-            DraggablePositionData::Anim(anim) => {
+            DraggablePositionData::Anim(rect) => {
+                let anim = get_transition_animation(grid.peek().to_container_rect(rect));
                 tracing::info!("ordering animation {:?}", anim.clone());
                 animation_controller.write().play_now(anim);
             }
-            DraggablePositionData::Rect(rect)
-                if animation_controller.peek().peek_is_finished()
-                    && (animation_controller.peek().peek_rect().is_none()
-                        || animation_controller
-                            .peek()
-                            .peek_rect()
-                            .is_some_and(|controller_rect| controller_rect != rect)) =>
-            {
-                animation_controller.write().set_rect(rect);
-                tracing::info!("set rect to:{:?}", rect.origin);
+            DraggablePositionData::Rect(rect) => {
+                let rect = grid.peek().to_container_rect(rect);
+                let moved = animation_controller
+                    .peek()
+                    .peek_rect()
+                    .map_or(true, |controller_rect| controller_rect.cast_unit() != rect);
+                if moved {
+                    animation_controller.write().set_rect(rect.cast_unit());
+                    tracing::info!("set rect to:{:?}", rect.origin);
+                }
             }
             _ => (),
         };
     };
 
+    // a stacked panel behind the active tab only shows its tab, unless it is being dragged
+    let hidden_tab = use_memo(move || {
+        let behind = layout_state
+            .read()
+            .get_tab_position(&id.peek())
+            .is_some_and(|tab| !tab.active);
+        behind
+            && !matches!(
+                local_drag_info.read().get_drag_state(),
+                DraggableStates::Grabbed(_)
+            )
+    });
+
     let display_state: String = use_memo(move || {
         let global_state = global_drag_info.peek().get_drag_state();
         let rect = current_rect
@@ -166,6 +942,9 @@ pub fn Draggable(
         if let Some(user_style) = &style {
             display_state.style = format!("{}\n {}", display_state.style, user_style);
         }
+        if hidden_tab() {
+            display_state.style = format!("{}{}", display_state.style, HIDDEN_TAB_STYLES);
+        }
         display_state.style
     })
     .to_string();
@@ -174,18 +953,30 @@ pub fn Draggable(
         Animatable {
             controller: animation_controller,
             style: display_state,
-            DragHandle {
-                title: id,
-                {handle}
-            }
-            Window {
-                //StateLogger{}
-                {children}
+            div {
+                style: "display: contents;",
+                role: "group",
+                aria_label: "{title}",
+                DragHandle {
+                    title: title.clone(),
+                    {handle}
+                }
+                div {
+                    style: if hidden_tab() { "display: none;" } else { "display: contents;" },
+                    Window {
+                        //StateLogger{}
+                        {children}
+                    }
+                }
             }
         }
     }
 }
 
+const HIDDEN_TAB_STYLES: &str = "
+    pointer-events: none;
+";
+
 const DRAG_HANDLE_STYLES: &str = "
     width: 100%;
     height: 2rem;
@@ -203,31 +994,284 @@ const DRAG_HANDLE_STYLES: &str = "
     align-items: center;
     padding-left: .5rem;
     text-transform: uppercase;
+    touch-action: pan-x pan-y;
+";
+
+const DRAG_HANDLE_PRESSING_STYLES: &str = "
+    background-color: var(--accent_0);
+    color: var(--bg);
+    transition-property: background-color, color;
+    transition-timing-function: linear;
+";
+
+const ARROW_KEYS: &[&str] = &["ArrowUp", "ArrowDown", "ArrowLeft", "ArrowRight"];
+
+const DRAG_HANDLE_GRABBED_STYLES: &str = "
+    touch-action: none;
+";
+
+const DRAG_HANDLE_TAB_STYLES: &str = "
+    flex-grow: 0;
+    pointer-events: auto;
+";
+
+const DRAG_HANDLE_ACTIVE_TAB_STYLES: &str = "
+    background-color: var(--bg);
 ";
 
 #[component]
 fn DragHandle(title: String, children: Element) -> Element {
     let mut global_drag_info = use_context::<Signal<GlobalDragState>>();
     let mut local_drag_info = use_context::<Signal<LocalDragState>>();
+    let mut layout_state = use_context::<Signal<LayoutState>>();
+    let mut handle = use_signal(|| None as Option<Rc<MountedData>>);
 
-    let mut start_drag = move |event: Event<PointerData>| {
-        let valid_drag = local_drag_info
-            .write()
-            .start_drag(event.data.element_coordinates());
+    // focus requested through the DragAreaController
+    use_effect(move || {
+        let panel_id = local_drag_info.peek().get_element_id();
+        if layout_state.read().get_focus_request() != Some(panel_id) {
+            return;
+        }
+        if let Some(handle) = handle() {
+            layout_state.write().request_focus(None);
+            spawn(async move {
+                let _ = handle.set_focus(true).await;
+            });
+        }
+    });
+
+    let config = use_context::<DragAreaConfig>();
+    let grid = use_context::<Signal<GridData>>();
+    let long_press_delay = config.long_press_delay;
+    let drag_distance = config.drag_distance;
+    let drag_delay = config.drag_delay;
+    let mut pending_press = use_signal(|| None as Option<PendingPress>);
+    let mut press_count = use_signal(|| 0u64);
+
+    let mut start_drag = move |pointer_id: i32,
+                               grab_point: Point2D<f64, ElementSpace>,
+                               current_pos: Point2D<f64, AreaSpace>| {
+        let valid_drag = local_drag_info.write().start_drag(grab_point);
 
         if let Ok(grab_data) = valid_drag {
+            if let Some(handle) = handle.peek().as_ref() {
+                capture_pointer(handle, pointer_id);
+            }
             global_drag_info.write().start_drag(DragAreaActiveDragData {
-                current_pos: event.data.client_coordinates().cast_unit(),
+                current_pos,
                 starting_data: grab_data.drag_origin,
+                panel_id: local_drag_info.peek().get_element_id(),
+                input: DragInput::Pointer,
+                pointer_id: Some(pointer_id),
+                drop_mode: DropMode::Swap,
             });
         }
     };
 
+    let mut finish_press = move || {
+        let press = pending_press.peek().clone();
+        if let Some(press) = press {
+            pending_press.set(None);
+            start_drag(press.pointer_id, press.grab_point, press.current_pos);
+        }
+    };
+
+    let mut abandon_press = move || {
+        if pending_press.peek().is_some() {
+            pending_press.set(None);
+            local_drag_info.write().cancel_press();
+        }
+    };
+
+    // a press only becomes a drag once it passes the distance or time threshold,
+    // so clicks still reach anything rendered inside the handle.
+    // touch always waits for a long press so the page can still scroll
+    let on_pointer_down = move |event: PointerEvent| {
+        if global_drag_info.peek().is_dragging() {
+            return;
+        }
+        event.stop_propagation();
+        // offsetX/Y are already in the handle's own unscaled pixels
+        let grab_point = event.data.element_coordinates();
+        let pos = grid.peek().to_area_point(event.data.client_coordinates());
+        let pointer_id = event.data.pointer_id();
+        let long_press = needs_long_press(&event.data.pointer_type());
+        if local_drag_info.write().press(grab_point).is_err() {
+            return;
+        }
+        let press_id = *press_count.peek() + 1;
+        press_count.set(press_id);
+        pending_press.set(Some(PendingPress::new(
+            press_id, pointer_id, grab_point, pos, long_press,
+        )));
+        let delay = match long_press {
+            true => Some(long_press_delay),
+            false if drag_distance <= 0. && drag_delay.is_none() => {
+                finish_press();
+                return;
+            }
+            false => drag_delay,
+        };
+        if let Some(delay) = delay {
+            spawn(async move {
+                sleep(delay).await;
+                match pending_press.peek().as_ref() {
+                    Some(press) if press.press_id == press_id => (),
+                    _ => return,
+                };
+                finish_press();
+            });
+        }
+    };
+
+    let on_pointer_move = move |event: PointerEvent| {
+        let pos = grid.peek().to_area_point(event.data.client_coordinates());
+        let progress = match pending_press.write().as_mut() {
+            Some(press) if press.pointer_id == event.data.pointer_id() => {
+                press.update_pos(pos, drag_distance)
+            }
+            _ => return,
+        };
+        match progress {
+            PressProgress::Waiting => (),
+            PressProgress::Start => finish_press(),
+            PressProgress::Abandon => abandon_press(),
+        }
+    };
+
+    // a mouse that leaves the handle mid-press is dragging faster than the threshold
+    let on_pointer_leave = move |_| {
+        let long_press = match pending_press.peek().as_ref() {
+            Some(press) => press.long_press,
+            None => return,
+        };
+        match long_press {
+            true => abandon_press(),
+            false => finish_press(),
+        }
+    };
+
+    let long_pressing = use_memo(move || {
+        pending_press
+            .read()
+            .as_ref()
+            .is_some_and(|press| press.long_press)
+    });
+
+    // in a tab stack the handle becomes this panel's tab along the tab bar
+    let tab = use_memo(move || {
+        layout_state
+            .read()
+            .get_tab_position(&local_drag_info.peek().get_element_id())
+    });
+
+    // the handle lets touches pan the page until the panel is picked up
+    let grabbed = use_memo(move || {
+        matches!(
+            local_drag_info.read().get_drag_state(),
+            DraggableStates::Grabbed(_)
+        )
+    });
+
+    let style = use_memo(move || {
+        let style = match (long_pressing(), grabbed()) {
+            (_, true) => format!("{}{}", DRAG_HANDLE_STYLES, DRAG_HANDLE_GRABBED_STYLES),
+            (true, false) => format!(
+                "{}{}\n transition-duration: {}ms;",
+                DRAG_HANDLE_STYLES,
+                DRAG_HANDLE_PRESSING_STYLES,
+                long_press_delay.as_millis()
+            ),
+            (false, false) => DRAG_HANDLE_STYLES.to_string(),
+        };
+        match tab() {
+            Some(tab) => format!(
+                "{}{}\n width: calc(100% / {}); margin-left: calc(100% * {} / {});{}",
+                style,
+                DRAG_HANDLE_TAB_STYLES,
+                tab.count,
+                tab.index,
+                tab.count,
+                if tab.active {
+                    DRAG_HANDLE_ACTIVE_TAB_STYLES
+                } else {
+                    ""
+                }
+            ),
+            None => style,
+        }
+    });
+
+    // keys pressed on buttons or inputs inside the handle bubble up through
+    // the handle, so they are marked on the way and ignored here
+    let mut child_key = use_hook(|| CopyValue::new(false));
+    use_key_guard(handle, &[" "], ARROW_KEYS);
+
+    // space or enter picks up and drops, arrows cycle through targets
+    let on_key_down = move |event: KeyboardEvent| {
+        if std::mem::take(&mut *child_key.write()) {
+            return;
+        }
+        let panel_id = local_drag_info.peek().get_element_id();
+        let holding = match global_drag_info.peek().get_drag_state() {
+            DragAreaStates::Dragging(drag_data) => {
+                drag_data.input == DragInput::Keyboard && drag_data.panel_id == panel_id
+            }
+            _ => false,
+        };
+        let is_select = matches!(event.data.key(), Key::Enter)
+            || matches!(event.data.key(), Key::Character(character) if character == " ");
+        match (holding, event.data.key()) {
+            (false, _) if is_select => {
+                DraggableStateController::start_keyboard_drag(global_drag_info, local_drag_info)
+            }
+            (true, _) if is_select => {
+                DraggableStateController::stop_drag(global_drag_info, layout_state)
+            }
+            (true, Key::ArrowRight | Key::ArrowDown) => {
+                DraggableStateController::step_keyboard_drag(global_drag_info, layout_state, 1)
+            }
+            (true, Key::ArrowLeft | Key::ArrowUp) => {
+                DraggableStateController::step_keyboard_drag(global_drag_info, layout_state, -1)
+            }
+            (_, _) => (),
+        }
+    };
+
     rsx! {
         div {
-            style: DRAG_HANDLE_STYLES,
-            onpointerdown: move |event| start_drag(event),
+            style: style,
+            tabindex: 0,
+            role: if tab().is_some() { "tab" } else { "button" },
+            aria_selected: tab().is_some_and(|tab| tab.active).to_string(),
+            aria_label: "Move {title}",
+            aria_grabbed: "{grabbed}",
+            onmounted: move |cx| handle.set(Some(cx.data())),
+            onpointerdown: on_pointer_down,
+            onpointermove: on_pointer_move,
+            onpointerup: move |_| {
+                // a click on a tab brings its panel to the front
+                if pending_press.peek().is_some() && tab.peek().is_some() {
+                    let panel_id = local_drag_info.peek().get_element_id();
+                    layout_state.write().select_tab(&panel_id);
+                }
+                abandon_press();
+            },
+            onpointercancel: move |_| abandon_press(),
+            onpointerleave: on_pointer_leave,
+            onlostpointercapture: move |event: PointerEvent| {
+                // capture is released after pointerup, so a drag still running here was interrupted
+                if global_drag_info.peek().is_dragged_by_pointer(event.data.pointer_id()) {
+                    DraggableStateController::cancel_drag(global_drag_info);
+                }
+            },
+            onkeydown: on_key_down,
             "{title}",
+            div {
+                style: "display: contents;",
+                onkeydown: move |_| child_key.set(true),
+                {children}
+            }
         }
     }
 }
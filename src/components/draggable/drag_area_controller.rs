@@ -0,0 +1,189 @@
+use super::{
+    AreaSpace, DragError, DragErrorType, DragEventKind, DraggableStateController, DropMode,
+    GlobalDragState, LayoutOperation, LayoutSnapshot, LayoutState, PanelDragEvent, PanelPlacement,
+    SnapInfo,
+};
+use dioxus::prelude::*;
+use dioxus_elements::geometry::euclid::Rect;
+
+pub fn use_drag_area() -> DragAreaController {
+    let global_drag_info = use_context::<Signal<GlobalDragState>>();
+    let layout_state = use_context::<Signal<LayoutState>>();
+    DragAreaController {
+        global_drag_info,
+        layout_state,
+    }
+}
+
+/// Moves panels from code. Every move is queued on the layout state and
+/// applied by the Draggable itself, so it animates exactly like a drop.
+#[derive(Clone, Copy, PartialEq)]
+pub struct DragAreaController {
+    global_drag_info: Signal<GlobalDragState>,
+    layout_state: Signal<LayoutState>,
+}
+
+impl DragAreaController {
+    /// Panels already in the target swap into the moved panel's old place, or
+    /// take it as a tab when the target is a tab stack.
+    pub fn move_to(&mut self, panel_id: &str, target_id: &str) -> Result<(), DragError> {
+        self.ensure_idle()?;
+        let operation = {
+            let layout = self.layout_state.peek();
+            let source = layout
+                .get_origin(panel_id)
+                .ok_or_else(|| DragError(DragErrorType::UnknownPanel(panel_id.to_string())))?;
+            let target_rect = layout
+                .get_target_rect(target_id)
+                .ok_or_else(|| DragError(DragErrorType::UnknownTarget(target_id.to_string())))?;
+            let mode = match layout.is_tab_stack(target_id) {
+                true => DropMode::Stack,
+                false => DropMode::Swap,
+            };
+            layout
+                .check_drop(panel_id, &source, target_id, mode)
+                .map_err(|rejection| DragError(DragErrorType::DropRejected(rejection)))?;
+            let destination = SnapInfo::new(Some(target_id.to_string()), target_rect);
+            match mode {
+                DropMode::Stack => {
+                    LayoutOperation::from_stack(panel_id.to_string(), source, destination)
+                }
+                _ => LayoutOperation::from_drop(panel_id.to_string(), source, destination, &layout),
+            }
+            .map(|operation| operation.with_source_stack(&layout))
+        };
+        if let Some(operation) = operation {
+            self.apply(operation.redo_placements());
+            if operation.stacked {
+                self.layout_state
+                    .write()
+                    .add_tab(target_id, panel_id.to_string(), None);
+            }
+            self.global_drag_info.write().record_operation(operation);
+        }
+        Ok(())
+    }
+
+    pub fn swap(&mut self, panel_a: &str, panel_b: &str) -> Result<(), DragError> {
+        let target_id = match self.layout_state.peek().get_panel(panel_b) {
+            Some(PanelPlacement::Snapped {
+                target_id: Some(target_id),
+                ..
+            }) => target_id.clone(),
+            Some(_) => return Err(DragError(DragErrorType::IllegalPlacement)),
+            None => return Err(DragError(DragErrorType::UnknownPanel(panel_b.to_string()))),
+        };
+        self.move_to(panel_a, &target_id)
+    }
+
+    /// Recorded and reported like a pointer release outside any target.
+    pub fn float(&mut self, panel_id: &str, rect: Rect<f64, AreaSpace>) -> Result<(), DragError> {
+        self.ensure_idle()?;
+        let operation = {
+            let layout = self.layout_state.peek();
+            let source = layout
+                .get_origin(panel_id)
+                .ok_or_else(|| DragError(DragErrorType::UnknownPanel(panel_id.to_string())))?;
+            LayoutOperation::from_release(panel_id.to_string(), source, rect)
+                .map(|operation| operation.with_source_stack(&layout))
+        };
+        if let Some(operation) = operation {
+            self.apply(operation.redo_placements());
+            let event = PanelDragEvent {
+                panel_id: operation.panel_id.clone(),
+                origin: operation.source.clone(),
+                destination: None,
+                pointer: rect.origin,
+            };
+            let mut global = self.global_drag_info.write();
+            global.emit(DragEventKind::Release, event);
+            global.record_operation(operation);
+        }
+        Ok(())
+    }
+
+    /// Falls back to the first empty target when the panel's home is occupied.
+    pub fn dock(&mut self, panel_id: &str) -> Result<(), DragError> {
+        self.ensure_idle()?;
+        let target_id = {
+            let layout = self.layout_state.peek();
+            match layout.get_panel(panel_id) {
+                Some(PanelPlacement::Snapped {
+                    target_id: Some(_), ..
+                }) => return Ok(()),
+                Some(_) => (),
+                None => return Err(DragError(DragErrorType::UnknownPanel(panel_id.to_string()))),
+            }
+            let home = layout
+                .get_home_target(panel_id)
+                .filter(|home| layout.get_panels_in_target(home).is_empty());
+            home.or_else(|| {
+                layout
+                    .get_targets_in_spatial_order()
+                    .into_iter()
+                    .find(|target_id| layout.get_panels_in_target(target_id).is_empty())
+            })
+            .ok_or(DragError(DragErrorType::NoTargetAvailable))?
+        };
+        self.move_to(panel_id, &target_id)
+    }
+
+    pub fn select_tab(&mut self, panel_id: &str) -> Result<(), DragError> {
+        self.ensure_panel(panel_id)?;
+        if self
+            .layout_state
+            .peek()
+            .get_tab_position(panel_id)
+            .is_none()
+        {
+            return Err(DragError(DragErrorType::IllegalPlacement));
+        }
+        self.layout_state.write().select_tab(panel_id);
+        Ok(())
+    }
+
+    pub fn focus(&mut self, panel_id: &str) -> Result<(), DragError> {
+        self.ensure_panel(panel_id)?;
+        self.layout_state
+            .write()
+            .request_focus(Some(panel_id.to_string()));
+        Ok(())
+    }
+
+    pub fn undo(&self) -> bool {
+        DraggableStateController::undo(self.global_drag_info, self.layout_state)
+    }
+
+    pub fn redo(&self) -> bool {
+        DraggableStateController::redo(self.global_drag_info, self.layout_state)
+    }
+
+    pub fn export(&self) -> LayoutSnapshot {
+        self.layout_state.peek().export()
+    }
+
+    pub fn import(&mut self, snapshot: LayoutSnapshot) {
+        self.layout_state.write().import(snapshot);
+    }
+
+    fn apply(&mut self, placements: Vec<(String, PanelPlacement)>) {
+        let mut layout = self.layout_state.write();
+        for (panel_id, placement) in placements {
+            layout.queue_placement(panel_id, placement);
+        }
+    }
+
+    fn ensure_idle(&self) -> Result<(), DragError> {
+        match self.global_drag_info.peek().is_dragging() {
+            true => Err(DragError(DragErrorType::IllegalPlacement)),
+            false => Ok(()),
+        }
+    }
+
+    fn ensure_panel(&self, panel_id: &str) -> Result<(), DragError> {
+        match self.layout_state.peek().get_panel(panel_id) {
+            Some(_) => Ok(()),
+            None => Err(DragError(DragErrorType::UnknownPanel(panel_id.to_string()))),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use super::{DragAreaConfig, GlobalDragState, IdRegistry, LayoutState};
+use dioxus::prelude::*;
+
+/// Lets Draggables move between every DragArea rendered inside it, sharing
+/// one drag and one layout between them.
+#[component]
+pub fn DragCoordinator(children: Element) -> Element {
+    let depth = try_use_context::<DragAreaConfig>().map_or(0, |config| config.depth + 1);
+    use_context_provider(|| SharedDragState {
+        depth,
+        global_drag_info: Signal::new(GlobalDragState::new()),
+        layout_state: Signal::new(LayoutState::new()),
+        ids: CopyValue::new(IdRegistry::new()),
+    });
+    rsx! {
+        {children}
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct SharedDragState {
+    /// Only areas at this depth join, so areas nested in its panels keep their own drags.
+    pub depth: usize,
+    pub global_drag_info: Signal<GlobalDragState>,
+    pub layout_state: Signal<LayoutState>,
+    pub ids: CopyValue<IdRegistry>,
+}
@@ -1,7 +1,9 @@
 use core::fmt;
 use std::fmt::{Display, Formatter};
 
-#[derive(Debug)]
+use super::{DropRejection, GridPlacement};
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct DragError(pub DragErrorType);
 
 impl Display for DragError {
@@ -10,15 +12,41 @@ impl Display for DragError {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum DragErrorType {
     IllegalDragStart,
+    IllegalPlacement,
+    DuplicateId(String),
+    ReservedId(String),
+    LayoutStore(String),
+    UnknownPanel(String),
+    UnknownTarget(String),
+    NoTargetAvailable,
+    DropRejected(DropRejection),
+    PlacementOutOfGrid(GridPlacement),
+    UnknownGridArea(String),
+    InvalidGridArea(String),
 }
 
 impl Display for DragErrorType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             Self::IllegalDragStart => write!(f, "Invalid conditions for Drag Start"),
+            Self::IllegalPlacement => write!(f, "Invalid conditions for Placement"),
+            Self::DuplicateId(id) => write!(f, "Id \"{}\" is used more than once", id),
+            Self::ReservedId(id) => write!(f, "Id \"{}\" uses the reserved character ':'", id),
+            Self::LayoutStore(message) => write!(f, "Layout store failed: {}", message),
+            Self::UnknownPanel(id) => write!(f, "No Draggable with id \"{}\"", id),
+            Self::UnknownTarget(id) => write!(f, "No DragTarget with id \"{}\"", id),
+            Self::NoTargetAvailable => write!(f, "No empty DragTarget available"),
+            Self::DropRejected(rejection) => write!(f, "Drop rejected: {}", rejection),
+            Self::PlacementOutOfGrid(placement) => {
+                write!(f, "Cell placement {} does not fit in the grid", placement)
+            }
+            Self::UnknownGridArea(name) => write!(f, "No grid area named \"{}\"", name),
+            Self::InvalidGridArea(name) => {
+                write!(f, "Grid area \"{}\" is not a filled rectangle", name)
+            }
         }
     }
 }
@@ -0,0 +1,144 @@
+use super::{
+    AreaSpace, DragAreaStates, DragEndings, DragOrigin, DropMode, LayoutOperation, LayoutState,
+    SnapInfo,
+};
+use dioxus::prelude::*;
+use dioxus_elements::geometry::euclid::Point2D;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PanelDragEvent {
+    pub panel_id: String,
+    pub origin: DragOrigin,
+    pub destination: Option<SnapInfo>,
+    pub pointer: Point2D<f64, AreaSpace>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum DragEventKind {
+    DragStart,
+    DragMove,
+    TargetEnter,
+    TargetLeave,
+    Drop,
+    Swap,
+    Release,
+    Cancel,
+}
+
+#[derive(Clone, Copy, Default, PartialEq)]
+pub(crate) struct DragEventHandlers {
+    pub on_drag_start: Option<EventHandler<PanelDragEvent>>,
+    pub on_drag_move: Option<EventHandler<PanelDragEvent>>,
+    pub on_target_enter: Option<EventHandler<PanelDragEvent>>,
+    pub on_target_leave: Option<EventHandler<PanelDragEvent>>,
+    pub on_drop: Option<EventHandler<PanelDragEvent>>,
+    pub on_swap: Option<EventHandler<PanelDragEvent>>,
+    pub on_release: Option<EventHandler<PanelDragEvent>>,
+    pub on_cancel: Option<EventHandler<PanelDragEvent>>,
+}
+
+impl DragEventHandlers {
+    pub fn call(&self, kind: DragEventKind, event: PanelDragEvent) {
+        let handler = match kind {
+            DragEventKind::DragStart => self.on_drag_start,
+            DragEventKind::DragMove => self.on_drag_move,
+            DragEventKind::TargetEnter => self.on_target_enter,
+            DragEventKind::TargetLeave => self.on_target_leave,
+            DragEventKind::Drop => self.on_drop,
+            DragEventKind::Swap => self.on_swap,
+            DragEventKind::Release => self.on_release,
+            DragEventKind::Cancel => self.on_cancel,
+        };
+        if let Some(handler) = handler {
+            handler.call(event);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct DragEventTracker {
+    drag_state: DragAreaStates,
+    snap_info: Option<SnapInfo>,
+    emitted_seen: u64,
+}
+
+impl DragEventTracker {
+    /// `emitted_seen` skips the events emitted before the tracker's area mounted.
+    pub fn new(emitted_seen: u64) -> Self {
+        Self {
+            drag_state: DragAreaStates::Initial,
+            snap_info: None,
+            emitted_seen,
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        drag_state: DragAreaStates,
+        snap_info: Option<SnapInfo>,
+        emitted: &[(u64, DragEventKind, PanelDragEvent)],
+        layout: &LayoutState,
+    ) -> Vec<(DragEventKind, PanelDragEvent)> {
+        let mut events: Vec<(DragEventKind, PanelDragEvent)> = emitted
+            .iter()
+            .filter(|(count, _, _)| *count > self.emitted_seen)
+            .map(|(_, kind, event)| (*kind, event.clone()))
+            .collect();
+        if let Some((count, _, _)) = emitted.last() {
+            self.emitted_seen = self.emitted_seen.max(*count);
+        }
+        match (&self.drag_state, &drag_state) {
+            (DragAreaStates::Dragging(old), DragAreaStates::Dragging(new)) => {
+                if old.current_pos != new.current_pos {
+                    events.push((DragEventKind::DragMove, new.to_event(snap_info.clone())));
+                }
+                if self.snap_info != snap_info {
+                    if let Some(left) = self.snap_info.clone() {
+                        events.push((DragEventKind::TargetLeave, new.to_event(Some(left))));
+                    }
+                    if let Some(entered) = snap_info.clone() {
+                        events.push((DragEventKind::TargetEnter, new.to_event(Some(entered))));
+                    }
+                }
+            }
+            (_, DragAreaStates::Dragging(new)) => {
+                events.push((DragEventKind::DragStart, new.to_event(None)));
+            }
+            (DragAreaStates::Dragging(old), DragAreaStates::Released(ending)) => match ending {
+                DragEndings::Snapping(destination) => {
+                    events.push((DragEventKind::Drop, old.to_event(Some(destination.clone()))));
+                    let operation = match old.drop_mode {
+                        DropMode::Swap => LayoutOperation::from_drop(
+                            old.panel_id.clone(),
+                            old.starting_data.clone(),
+                            destination.clone(),
+                            layout,
+                        ),
+                        DropMode::Stack | DropMode::Split(_) => None,
+                    };
+                    for displaced in operation.map_or(Vec::new(), |op| op.displaced) {
+                        events.push((
+                            DragEventKind::Swap,
+                            PanelDragEvent {
+                                panel_id: displaced.panel_id,
+                                origin: DragOrigin::Snapped(displaced.from),
+                                destination: Some(displaced.to),
+                                pointer: old.current_pos,
+                            },
+                        ));
+                    }
+                }
+                DragEndings::Releasing(_) => {
+                    events.push((DragEventKind::Release, old.to_event(None)));
+                }
+                DragEndings::Cancelling(_) => {
+                    events.push((DragEventKind::Cancel, old.to_event(None)));
+                }
+            },
+            (_, _) => (),
+        }
+        self.drag_state = drag_state;
+        self.snap_info = snap_info;
+        events
+    }
+}
@@ -1,50 +1,45 @@
 use dx_flipbook::controllers::AnimationBuilder;
 
-use super::SnapInfo;
+use super::{AreaSpace, SnapInfo};
+use dioxus_elements::geometry::euclid::Rect;
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct DraggableTransitionData {
     pub from: SnapInfo,
     pub to: SnapInfo,
-    pub anim: AnimationBuilder,
     pub mode: DraggableTransitionMode,
     id: String,
 }
 
 impl DraggableTransitionData {
     pub fn new(from: SnapInfo, to: SnapInfo, mode: DraggableTransitionMode, id: String) -> Self {
-        let anim = AnimationBuilder::default()
-            .animate_to(to.rect)
-            .with_duration(web_time::Duration::from_millis(500))
-            .with_easing(dx_flipbook::easing::Easing::QuadOut);
-        Self {
-            from,
-            to,
-            anim,
-            mode,
-            id,
-        }
+        Self { from, to, mode, id }
     }
 
     pub fn reverse(&self) -> DraggableTransitionData {
-        let new_anim = AnimationBuilder::default()
-            .animate_to(self.from.rect.clone())
-            .with_duration(web_time::Duration::from_millis(500))
-            .with_easing(dx_flipbook::easing::Easing::QuadOut);
         DraggableTransitionData {
             from: self.to.clone(),
             to: self.from.clone(),
-            anim: new_anim,
             mode: self.mode.reverse(),
             id: self.id.clone(),
         }
     }
 }
 
+/// The animation moving a panel to `rect`, given inside its area's Container.
+pub(crate) fn get_transition_animation(rect: Rect<f64, AreaSpace>) -> AnimationBuilder {
+    AnimationBuilder::default()
+        .animate_to(rect.cast_unit())
+        .with_duration(web_time::Duration::from_millis(500))
+        .with_easing(dx_flipbook::easing::Easing::QuadOut)
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum DraggableTransitionMode {
     Avoidance,
     Resting,
+    /// Moving a free panel, which rests released where it lands.
+    Releasing,
 }
 
 impl DraggableTransitionMode {
@@ -52,6 +47,7 @@ impl DraggableTransitionMode {
         match self {
             Self::Avoidance => Self::Resting,
             Self::Resting => Self::Avoidance,
+            Self::Releasing => Self::Releasing,
         }
     }
 }
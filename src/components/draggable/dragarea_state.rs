@@ -1,7 +1,39 @@
-use super::{DragOrigin, SnapInfo};
+use super::targets::TAB_STRIP_HEIGHT;
+use super::{
+    AreaSpace, DragError, DragErrorType, DragEventKind, DragOrigin, DropRejection, Edge,
+    LayoutHistory, LayoutOperation, LayoutState, LocalDragState, PanelDragEvent, SnapInfo,
+    TargetSplit,
+};
 use dioxus::prelude::*;
 use dioxus_elements::geometry::euclid::Point2D;
 
+const KEYBOARD_GRAB_POINT: (f64, f64) = (100., 16.);
+const DEFAULT_LONG_PRESS_MS: u64 = 400;
+const DEFAULT_DRAG_DISTANCE: f64 = 4.;
+const MAX_EMITTED_EVENTS: usize = 16;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DragAreaConfig {
+    pub area_id: String,
+    pub depth: usize,
+    pub long_press_delay: web_time::Duration,
+    /// How far, or how long, a mouse or pen press goes before it becomes a drag.
+    pub drag_distance: f64,
+    pub drag_delay: Option<web_time::Duration>,
+}
+
+impl Default for DragAreaConfig {
+    fn default() -> Self {
+        Self {
+            area_id: String::new(),
+            depth: 0,
+            long_press_delay: web_time::Duration::from_millis(DEFAULT_LONG_PRESS_MS),
+            drag_distance: DEFAULT_DRAG_DISTANCE,
+            drag_delay: None,
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum DragAreaStates {
     Initial,
@@ -11,32 +43,249 @@ pub enum DragAreaStates {
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct DragAreaActiveDragData {
-    pub current_pos: Point2D<f64, f64>,
+    pub current_pos: Point2D<f64, AreaSpace>,
     pub starting_data: DragOrigin,
+    pub panel_id: String,
+    pub input: DragInput,
+    pub pointer_id: Option<i32>,
+    pub drop_mode: DropMode,
+}
+
+/// What a drop onto the hovered target does.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum DropMode {
+    /// Panels resting in the target swap into the grabbed panel's origin.
+    #[default]
+    Swap,
+    /// The grabbed panel joins the target as a tab.
+    Stack,
+    /// The target splits along an edge and the grabbed panel takes the new half.
+    Split(Edge),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DragInput {
+    Pointer,
+    Keyboard,
 }
 
 impl DragAreaActiveDragData {
-    pub fn update_current_pos(&mut self, new_pos: Point2D<f64, f64>) {
+    pub fn update_current_pos(&mut self, new_pos: Point2D<f64, AreaSpace>) {
         self.current_pos = new_pos;
     }
+
+    pub fn to_event(&self, destination: Option<SnapInfo>) -> PanelDragEvent {
+        PanelDragEvent {
+            panel_id: self.panel_id.clone(),
+            origin: self.starting_data.clone(),
+            destination,
+            pointer: self.current_pos,
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Debug)]
 pub enum DragEndings {
     Snapping(SnapInfo),
-    Releasing(Point2D<f64, f64>),
+    Releasing(Point2D<f64, AreaSpace>),
+    Cancelling(Point2D<f64, AreaSpace>),
 }
 pub struct DraggableStateController;
 
 impl DraggableStateController {
-    pub fn stop_drag(mut global_drag_info: Signal<GlobalDragState>) {
+    /// A drop over a target that refuses the panel sends it back to its origin.
+    pub fn stop_drag(
+        mut global_drag_info: Signal<GlobalDragState>,
+        mut layout_state: Signal<LayoutState>,
+    ) {
+        let drag_data = match global_drag_info.peek().get_drag_state() {
+            DragAreaStates::Dragging(drag_data) => drag_data,
+            _ => return,
+        };
+        let destination = global_drag_info
+            .peek()
+            .get_snap_info()
+            .and_then(|snap_info| Some((snap_info.target_id?, snap_info.rect)));
+        let rejection = global_drag_info.write().stop_drag(&layout_state.peek());
+        if let Some(rejection) = rejection {
+            layout_state
+                .write()
+                .report_error(DragError(DragErrorType::DropRejected(rejection)));
+            return;
+        }
+        let (target_id, rect) = match destination {
+            Some(destination) => destination,
+            None => return,
+        };
+        let mut layout = layout_state.write();
+        match drag_data.drop_mode {
+            // the tab lands where it was dropped along the tab strip
+            DropMode::Stack => {
+                let count = layout
+                    .get_panels_in_target(&target_id)
+                    .iter()
+                    .filter(|panel_id| **panel_id != drag_data.panel_id)
+                    .count()
+                    + 1;
+                let offset = (drag_data.current_pos.x - rect.origin.x) / rect.width();
+                let index = (offset * count as f64).floor().max(0.) as usize;
+                layout.add_tab(&target_id, drag_data.panel_id, Some(index));
+            }
+            // panels swapped into a tab stack take the dragged tab's place
+            DropMode::Swap => {
+                let origin_target = match drag_data.starting_data.get_snap_info().target_id {
+                    Some(origin_target) if origin_target != target_id => origin_target,
+                    _ => return,
+                };
+                if !layout.is_tab_stack(&origin_target) {
+                    return;
+                }
+                for displaced_id in layout.get_panels_in_target(&target_id) {
+                    if displaced_id != drag_data.panel_id {
+                        layout.add_tab(&origin_target, displaced_id, None);
+                    }
+                }
+            }
+            DropMode::Split(edge) => layout.add_split(TargetSplit::new(target_id, edge)),
+        }
+    }
+
+    pub fn start_keyboard_drag(
+        mut global_drag_info: Signal<GlobalDragState>,
+        mut local_drag_info: Signal<LocalDragState>,
+    ) {
         if global_drag_info.peek().is_dragging() {
-            global_drag_info.write().stop_drag();
+            return;
+        }
+        let grab_point = Point2D::new(KEYBOARD_GRAB_POINT.0, KEYBOARD_GRAB_POINT.1);
+        let valid_drag = local_drag_info.write().start_drag(grab_point);
+        if let Ok(grab_data) = valid_drag {
+            let current_pos = grab_data.drag_origin.get_snap_info().rect.center();
+            global_drag_info.write().start_drag(DragAreaActiveDragData {
+                current_pos,
+                starting_data: grab_data.drag_origin,
+                panel_id: local_drag_info.peek().get_element_id(),
+                input: DragInput::Keyboard,
+                pointer_id: None,
+                drop_mode: DropMode::Swap,
+            });
         }
     }
+
+    /// Moves a keyboard drag `step` targets forward or back in spatial order.
+    pub fn step_keyboard_drag(
+        mut global_drag_info: Signal<GlobalDragState>,
+        layout_state: Signal<LayoutState>,
+        step: isize,
+    ) {
+        if global_drag_info.peek().get_drag_input() != Some(DragInput::Keyboard) {
+            return;
+        }
+        let drag_data = match global_drag_info.peek().get_drag_state() {
+            DragAreaStates::Dragging(drag_data) => drag_data,
+            _ => return,
+        };
+        let layout = layout_state.peek();
+        // a tabs target holding another panel has a second stop on its tab
+        // strip, where the drop stacks the panels instead of swapping them
+        let mut stops = Vec::new();
+        for target_id in layout.get_targets_in_spatial_order() {
+            let rect = match layout.get_target_rect(&target_id) {
+                Some(rect) => rect,
+                None => continue,
+            };
+            let accepts = |mode| {
+                layout.accepts_drop(
+                    &drag_data.panel_id,
+                    &drag_data.starting_data,
+                    &target_id,
+                    mode,
+                )
+            };
+            let stacked = layout.is_tab_stack(&target_id);
+            let mode = match stacked {
+                true => DropMode::Stack,
+                false => DropMode::Swap,
+            };
+            if accepts(mode) {
+                stops.push(rect.center());
+            }
+            let occupied = layout
+                .get_panels_in_target(&target_id)
+                .iter()
+                .any(|panel_id| *panel_id != drag_data.panel_id);
+            if !stacked && occupied && layout.takes_tabs(&target_id) && accepts(DropMode::Stack) {
+                let strip = (TAB_STRIP_HEIGHT / 2.).min(rect.height() / 2.);
+                stops.push(Point2D::new(rect.center().x, rect.origin.y + strip));
+            }
+        }
+        if stops.is_empty() {
+            return;
+        }
+        let current = stops.iter().position(|stop| *stop == drag_data.current_pos);
+        let next = match current {
+            Some(index) => (index as isize + step).rem_euclid(stops.len() as isize) as usize,
+            None => 0,
+        };
+        global_drag_info.write().update_drag(stops[next]);
+    }
+
+    pub fn cancel_drag(mut global_drag_info: Signal<GlobalDragState>) {
+        if global_drag_info.peek().is_dragging() {
+            global_drag_info.write().cancel_drag();
+        }
+    }
+
+    /// Returns false when there is nothing to undo.
+    pub fn undo(
+        mut global_drag_info: Signal<GlobalDragState>,
+        mut layout_state: Signal<LayoutState>,
+    ) -> bool {
+        if global_drag_info.peek().is_dragging() {
+            return false;
+        }
+        let operation = match global_drag_info.write().undo() {
+            Some(operation) => operation,
+            None => return false,
+        };
+        let mut layout = layout_state.write();
+        for (panel_id, placement) in operation.undo_placements() {
+            layout.queue_placement(panel_id, placement);
+        }
+        if let Some((target_id, stack)) = operation.source_stack.clone() {
+            layout.restore_tab_stack(target_id, stack);
+        }
+        true
+    }
+
+    /// Returns false when there is nothing to redo.
+    pub fn redo(
+        mut global_drag_info: Signal<GlobalDragState>,
+        mut layout_state: Signal<LayoutState>,
+    ) -> bool {
+        if global_drag_info.peek().is_dragging() {
+            return false;
+        }
+        let operation = match global_drag_info.write().redo() {
+            Some(operation) => operation,
+            None => return false,
+        };
+        let mut layout = layout_state.write();
+        for (panel_id, placement) in operation.redo_placements() {
+            layout.queue_placement(panel_id, placement);
+        }
+        if let Some(split) = operation.split.clone() {
+            layout.add_split(split);
+        }
+        if let (true, Some(target_id)) = (operation.stacked, &operation.destination.target_id) {
+            layout.add_tab(target_id, operation.panel_id.clone(), None);
+        }
+        true
+    }
 }
 
 const DRAG_AREA_BASE_STYLES: &str = "
+    position: relative;
     background-image: radial-gradient(black .05rem, transparent 0);
     background-size: .6rem .6rem;
     width: 100%;
@@ -46,12 +295,17 @@ const DRAG_AREA_BASE_STYLES: &str = "
 const DRAG_AREA_ACTIVE_STYLES: &str = "
     -webkit-user-select: none;
     user-select: none;
+    touch-action: none;
 ";
 
 #[derive(Clone)]
 pub struct GlobalDragState {
     drag_state: DragAreaStates,
     snap_info: Option<SnapInfo>,
+    rejection: Option<DropRejection>,
+    history: LayoutHistory,
+    emitted: Vec<(u64, DragEventKind, PanelDragEvent)>,
+    emitted_count: u64,
 }
 
 impl GlobalDragState {
@@ -59,6 +313,24 @@ impl GlobalDragState {
         Self::default()
     }
 
+    /// Lifecycle events of moves made from code, numbered so every area
+    /// reports each of them once.
+    pub(crate) fn emit(&mut self, kind: DragEventKind, event: PanelDragEvent) {
+        self.emitted_count += 1;
+        self.emitted.push((self.emitted_count, kind, event));
+        if self.emitted.len() > MAX_EMITTED_EVENTS {
+            self.emitted.remove(0);
+        }
+    }
+
+    pub(crate) fn get_emitted(&self) -> &[(u64, DragEventKind, PanelDragEvent)] {
+        &self.emitted
+    }
+
+    pub(crate) fn get_emitted_count(&self) -> u64 {
+        self.emitted_count
+    }
+
     pub fn get_drag_state(&self) -> DragAreaStates {
         self.drag_state.clone()
     }
@@ -72,28 +344,156 @@ impl GlobalDragState {
         self.snap_info.clone()
     }
 
-    fn stop_drag(&mut self) {
+    pub fn set_rejection(&mut self, rejection: Option<DropRejection>) {
+        self.rejection = rejection;
+    }
+
+    pub fn get_rejection(&self) -> Option<DropRejection> {
+        self.rejection.clone()
+    }
+
+    pub fn set_drop_mode(&mut self, drop_mode: DropMode) {
+        if let DragAreaStates::Dragging(drag_data) = &mut self.drag_state {
+            drag_data.drop_mode = drop_mode;
+        }
+    }
+
+    pub fn get_drop_mode(&self) -> DropMode {
+        match &self.drag_state {
+            DragAreaStates::Dragging(drag_data) => drag_data.drop_mode,
+            _ => DropMode::Swap,
+        }
+    }
+
+    fn stop_drag(&mut self, layout: &LayoutState) -> Option<DropRejection> {
+        if let (DragAreaStates::Dragging(drag_data), None, Some(rejection)) = (
+            self.drag_state.clone(),
+            &self.snap_info,
+            self.rejection.take(),
+        ) {
+            self.drag_state =
+                DragAreaStates::Released(DragEndings::Cancelling(drag_data.current_pos));
+            tracing::info!("rejected drop {:?}", rejection);
+            return Some(rejection);
+        }
         if let DragAreaStates::Dragging(drag_data) = self.drag_state.clone() {
             self.drag_state = match self.snap_info.clone() {
-                Some(info) => DragAreaStates::Released(DragEndings::Snapping(info)),
+                Some(info) => {
+                    // a split drop lands in the half the new target will take
+                    let (info, split) = match (drag_data.drop_mode, info.target_id.clone()) {
+                        (DropMode::Split(edge), Some(target_id)) => {
+                            let split = TargetSplit::new(target_id, edge);
+                            let rect = edge.split_rect(info.rect);
+                            (
+                                SnapInfo::new(Some(split.target_id.clone()), rect),
+                                Some(split),
+                            )
+                        }
+                        _ => (info, None),
+                    };
+                    let operation = match drag_data.drop_mode {
+                        DropMode::Swap => LayoutOperation::from_drop(
+                            drag_data.panel_id,
+                            drag_data.starting_data,
+                            info.clone(),
+                            layout,
+                        ),
+                        DropMode::Stack => LayoutOperation::from_stack(
+                            drag_data.panel_id,
+                            drag_data.starting_data,
+                            info.clone(),
+                        ),
+                        DropMode::Split(_) => split.map(|split| {
+                            LayoutOperation::from_split(
+                                drag_data.panel_id,
+                                drag_data.starting_data,
+                                info.clone(),
+                                split,
+                            )
+                        }),
+                    };
+                    if let Some(operation) = operation {
+                        self.history.record(operation.with_source_stack(layout));
+                    }
+                    DragAreaStates::Released(DragEndings::Snapping(info))
+                }
                 None => DragAreaStates::Released(DragEndings::Releasing(drag_data.current_pos)),
             };
             tracing::info!("ending drag {:?}", self.drag_state);
         }
+        None
+    }
+
+    pub fn record_operation(&mut self, operation: LayoutOperation) {
+        self.history.record(operation);
+    }
+
+    fn cancel_drag(&mut self) {
+        if let DragAreaStates::Dragging(drag_data) = self.drag_state.clone() {
+            self.drag_state =
+                DragAreaStates::Released(DragEndings::Cancelling(drag_data.current_pos));
+            self.snap_info = None;
+            self.rejection = None;
+            tracing::info!("cancelling drag {:?}", self.drag_state);
+        }
+    }
+
+    fn undo(&mut self) -> Option<LayoutOperation> {
+        self.history.undo()
+    }
+
+    fn redo(&mut self) -> Option<LayoutOperation> {
+        self.history.redo()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
     }
 
     pub fn is_dragging(&self) -> bool {
         matches!(self.drag_state, DragAreaStates::Dragging(_))
     }
 
+    pub fn is_pointer_drag(&self) -> bool {
+        matches!(&self.drag_state, DragAreaStates::Dragging(drag_data) if drag_data.input == DragInput::Pointer)
+    }
+
+    pub fn is_dragged_by_pointer(&self, pointer_id: i32) -> bool {
+        match &self.drag_state {
+            DragAreaStates::Dragging(drag_data) => {
+                drag_data.input == DragInput::Pointer && drag_data.pointer_id == Some(pointer_id)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn get_dragged_panel(&self) -> Option<String> {
+        match &self.drag_state {
+            DragAreaStates::Dragging(drag_data) => Some(drag_data.panel_id.clone()),
+            _ => None,
+        }
+    }
+
+    pub fn get_drag_input(&self) -> Option<DragInput> {
+        match &self.drag_state {
+            DragAreaStates::Dragging(drag_data) => Some(drag_data.input),
+            _ => None,
+        }
+    }
+
     pub fn start_drag(&mut self, drag_data: DragAreaActiveDragData) -> &mut Self {
         if let DragAreaStates::Initial | DragAreaStates::Released(_) = self.drag_state {
+            self.rejection = None;
             self.drag_state = DragAreaStates::Dragging(drag_data);
         }
         self
     }
 
-    pub fn update_drag(&mut self, pos: Point2D<f64, f64>) {
+    pub fn update_drag(&mut self, pos: Point2D<f64, AreaSpace>) {
         if let DragAreaStates::Dragging(mut drag_data) = self.drag_state.clone() {
             drag_data.update_current_pos(pos);
             self.drag_state = DragAreaStates::Dragging(drag_data);
@@ -113,6 +513,10 @@ impl Default for GlobalDragState {
         Self {
             drag_state: DragAreaStates::Initial,
             snap_info: None,
+            rejection: None,
+            history: LayoutHistory::new(),
+            emitted: Vec::new(),
+            emitted_count: 0,
         }
     }
 }
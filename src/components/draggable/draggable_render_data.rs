@@ -1,6 +1,6 @@
+use super::AreaSpace;
 use dioxus::prelude::*;
 use dioxus_elements::geometry::euclid::Rect;
-use dx_flipbook::controllers::AnimationBuilder;
 
 const DRAGGABLE_BASE_STYLES: &str = "
     display: flex;
@@ -38,8 +38,8 @@ pub struct DraggableRenderData {
 #[derive(Clone, Debug)]
 pub enum DraggablePositionData {
     Default,
-    Rect(Rect<f64, f64>),
-    Anim(AnimationBuilder),
+    Rect(Rect<f64, AreaSpace>),
+    Anim(Rect<f64, AreaSpace>),
 }
 
 impl Default for DraggableRenderData {
@@ -52,24 +52,31 @@ impl Default for DraggableRenderData {
 }
 
 impl DraggableRenderData {
-    pub(crate) fn transitioning(anim: AnimationBuilder) -> Self {
+    pub(crate) fn transitioning(rect: Rect<f64, AreaSpace>) -> Self {
         Self {
             style: format!(
                 "{}{}{}",
                 DRAGGABLE_BASE_STYLES, SNAPPED_DRAGGABLE_STYLES, TRANSITIONING_DRAGGABLE_STYLES
             ),
-            position_data: DraggablePositionData::Anim(anim),
+            position_data: DraggablePositionData::Anim(rect),
         }
     }
 
-    pub(crate) fn snapped(rect: Rect<f64, f64>) -> Self {
+    pub(crate) fn snapped(rect: Rect<f64, AreaSpace>) -> Self {
         Self {
             style: format!("{}{}", DRAGGABLE_BASE_STYLES, SNAPPED_DRAGGABLE_STYLES),
             position_data: DraggablePositionData::Rect(rect),
         }
     }
 
-    pub(crate) fn free_or_dragging(rect: Rect<f64, f64>) -> Self {
+    pub(crate) fn returning(rect: Rect<f64, AreaSpace>) -> Self {
+        Self {
+            style: format!("{}{}", DRAGGABLE_BASE_STYLES, DRAGGABLE_DRAG_STYLES),
+            position_data: DraggablePositionData::Anim(rect),
+        }
+    }
+
+    pub(crate) fn free_or_dragging(rect: Rect<f64, AreaSpace>) -> Self {
         Self {
             style: format!("{}{}", DRAGGABLE_BASE_STYLES, DRAGGABLE_DRAG_STYLES),
             position_data: DraggablePositionData::Rect(rect),
@@ -1,6 +1,6 @@
 use super::{
-    DragAreaActiveDragData, DragAreaStates, DragEndings, DragOrigin, DraggableTransitionData,
-    DraggableTransitionMode, GridData, SnapInfo,
+    AreaSpace, DragAreaActiveDragData, DragAreaStates, DragEndings, DragOrigin,
+    DraggableTransitionData, DraggableTransitionMode, DropMode, PanelPlacement, SnapInfo,
 };
 use crate::components::{
     draggable::DraggableRenderData, DragError, DragErrorType, DraggableVariants,
@@ -14,6 +14,7 @@ use dioxus_elements::geometry::{
 #[derive(Clone, Debug, PartialEq)]
 pub enum DraggableStates {
     Initial,
+    Pending(DraggableGrabData),
     Grabbed(DraggableGrabData),
     Resting(DraggableRestStates),
 }
@@ -26,7 +27,7 @@ pub struct DraggableGrabData {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum DraggableRestStates {
-    Released(Rect<f64, f64>),
+    Released(Rect<f64, AreaSpace>),
     Snapped(DraggableSnapStates),
 }
 
@@ -74,22 +75,109 @@ impl LocalDragState {
         self.drag_state = DraggableStates::Resting(rest);
     }
 
-    pub fn resize_snapped(&mut self, grid: GridData) {
-        tracing::info!("resizing {}", self.id);
-        if let DraggableStates::Resting(DraggableRestStates::Snapped(snap_state)) = &self.drag_state
-        {
-            let mut snap = match snap_state {
-                DraggableSnapStates::Final(snap_info) => snap_info.clone(),
-                DraggableSnapStates::Preview(preview_data) => preview_data.to.clone(),
-                DraggableSnapStates::Transitioning(_transition) => return,
-            };
-            snap.rect = grid.get_new_child_rect(snap.rect);
-            self.drag_state = DraggableStates::Resting(DraggableRestStates::Snapped(
-                DraggableSnapStates::Final(snap.clone()),
-            ));
-            tracing::info!("resized:{:?}", self.drag_state);
-        } else {
-            tracing::error!("could not resize");
+    /// None while the draggable is grabbed or moving.
+    pub fn get_placement(&self) -> Option<PanelPlacement> {
+        match &self.drag_state {
+            DraggableStates::Resting(DraggableRestStates::Snapped(DraggableSnapStates::Final(
+                snap_info,
+            ))) => Some(PanelPlacement::Snapped {
+                target_id: snap_info.target_id.clone(),
+                rect: snap_info.rect.into(),
+            }),
+            DraggableStates::Resting(DraggableRestStates::Released(rect)) => {
+                Some(PanelPlacement::Released {
+                    rect: (*rect).into(),
+                })
+            }
+            DraggableStates::Pending(grab_data) => Some(grab_data.drag_origin.clone().into()),
+            _ => None,
+        }
+    }
+
+    pub fn place(&mut self, rest: DraggableRestStates) -> Result<(), DragError> {
+        let current = match self.drag_state.clone() {
+            DraggableStates::Initial => {
+                self.drag_state = DraggableStates::Resting(rest);
+                return Ok(());
+            }
+            DraggableStates::Resting(DraggableRestStates::Snapped(DraggableSnapStates::Final(
+                snap_info,
+            ))) => snap_info,
+            DraggableStates::Resting(DraggableRestStates::Released(rect)) => {
+                SnapInfo::new(None, rect)
+            }
+            _ => return Err(DragError(DragErrorType::IllegalPlacement)),
+        };
+
+        self.drag_state = match rest {
+            DraggableRestStates::Snapped(DraggableSnapStates::Final(to)) if to != current => {
+                DraggableStates::Resting(DraggableRestStates::Snapped(
+                    DraggableSnapStates::Transitioning(DraggableTransitionData::new(
+                        current,
+                        to,
+                        DraggableTransitionMode::Resting,
+                        self.id.clone(),
+                    )),
+                ))
+            }
+            DraggableRestStates::Released(to) if to != current.rect => {
+                DraggableStates::Resting(DraggableRestStates::Snapped(
+                    DraggableSnapStates::Transitioning(DraggableTransitionData::new(
+                        current,
+                        SnapInfo::new(None, to),
+                        DraggableTransitionMode::Releasing,
+                        self.id.clone(),
+                    )),
+                ))
+            }
+            rest => DraggableStates::Resting(rest),
+        };
+        tracing::info!("placed {:?}: {:?}", self.id, self.drag_state);
+        Ok(())
+    }
+
+    /// Follows a resized target without animating.
+    pub fn resize_snapped(&mut self, target: SnapInfo) {
+        let snap = match &self.drag_state {
+            DraggableStates::Resting(DraggableRestStates::Snapped(DraggableSnapStates::Final(
+                snap_info,
+            ))) => snap_info,
+            _ => return,
+        };
+        if snap.target_id != target.target_id || snap.rect == target.rect {
+            return;
+        }
+        tracing::info!("resizing {} to {:?}", self.id, target.rect);
+        self.drag_state = DraggableStates::Resting(DraggableRestStates::Snapped(
+            DraggableSnapStates::Final(target),
+        ));
+    }
+
+    pub fn press(
+        &mut self,
+        grab_point: Point2D<f64, ElementSpace>,
+    ) -> Result<DraggableGrabData, DragError> {
+        let grab_data = DraggableGrabData {
+            grab_point,
+            drag_origin: self.get_drag_origin()?,
+        };
+        self.drag_state = DraggableStates::Pending(grab_data.clone());
+        Ok(grab_data)
+    }
+
+    pub fn cancel_press(&mut self) {
+        if let DraggableStates::Pending(grab_data) = self.drag_state.clone() {
+            self.drag_state =
+                DraggableStates::Resting(Self::get_origin_rest_state(grab_data.drag_origin));
+        }
+    }
+
+    fn get_origin_rest_state(drag_origin: DragOrigin) -> DraggableRestStates {
+        match drag_origin {
+            DragOrigin::Snapped(snap) => {
+                DraggableRestStates::Snapped(DraggableSnapStates::Final(snap))
+            }
+            DragOrigin::Free(rect) => DraggableRestStates::Released(rect),
         }
     }
 
@@ -98,10 +186,27 @@ impl LocalDragState {
         grab_point: Point2D<f64, ElementSpace>,
     ) -> Result<DraggableGrabData, DragError> {
         tracing::info!("start data: {:?}", self.drag_state);
+        let grab_data = match self.drag_state.clone() {
+            DraggableStates::Pending(grab_data) => grab_data,
+            _ => DraggableGrabData {
+                grab_point,
+                drag_origin: self.get_drag_origin()?,
+            },
+        };
+        self.drag_state = DraggableStates::Grabbed(grab_data.clone());
+        Ok(grab_data)
+    }
+
+    fn get_drag_origin(&self) -> Result<DragOrigin, DragError> {
         let drag_origin = match self.drag_state.clone() {
             DraggableStates::Resting(rest) => match rest {
                 DraggableRestStates::Snapped(snap_data) => match snap_data {
                     DraggableSnapStates::Final(final_snap) => DragOrigin::Snapped(final_snap),
+                    DraggableSnapStates::Transitioning(transition)
+                        if transition.mode == DraggableTransitionMode::Releasing =>
+                    {
+                        DragOrigin::Free(transition.to.rect)
+                    }
                     DraggableSnapStates::Transitioning(transition) => {
                         DragOrigin::Snapped(transition.to)
                     }
@@ -114,12 +219,7 @@ impl LocalDragState {
             },
             _ => return Err(DragError(DragErrorType::IllegalDragStart)),
         };
-        let grab_data = DraggableGrabData {
-            grab_point,
-            drag_origin,
-        };
-        self.drag_state = DraggableStates::Grabbed(grab_data.clone());
-        Ok(grab_data)
+        Ok(drag_origin)
     }
 
     fn get_transition_end_state(
@@ -128,14 +228,25 @@ impl LocalDragState {
     ) -> DraggableSnapStates {
         match transition.mode {
             DraggableTransitionMode::Avoidance => DraggableSnapStates::Preview(transition),
-            DraggableTransitionMode::Resting => DraggableSnapStates::Final(transition.to),
+            DraggableTransitionMode::Resting | DraggableTransitionMode::Releasing => {
+                DraggableSnapStates::Final(transition.to)
+            }
         }
     }
 
-    pub fn update_state(&mut self, global_drag_state: DragAreaStates, rect: Rect<f64, f64>) {
+    pub fn update_state(&mut self, global_drag_state: DragAreaStates, rect: Rect<f64, AreaSpace>) {
         let old = self.drag_state.clone();
         match (self.drag_state.clone(), global_drag_state) {
             (DraggableStates::Initial, _) => return,
+            (
+                DraggableStates::Resting(DraggableRestStates::Snapped(
+                    DraggableSnapStates::Transitioning(transition),
+                )),
+                _,
+            ) if transition.mode == DraggableTransitionMode::Releasing => {
+                self.drag_state =
+                    DraggableStates::Resting(DraggableRestStates::Released(transition.to.rect));
+            }
             (
                 DraggableStates::Resting(DraggableRestStates::Snapped(
                     DraggableSnapStates::Transitioning(transition),
@@ -179,7 +290,7 @@ impl LocalDragState {
         &mut self,
         draggable_grab_data: DraggableGrabData,
         drag_area_dragging_state: DragEndings,
-        rect: Rect<f64, f64>,
+        rect: Rect<f64, AreaSpace>,
     ) {
         self.drag_state = match drag_area_dragging_state {
             DragEndings::Releasing(pointer_position) => {
@@ -188,19 +299,20 @@ impl LocalDragState {
             DragEndings::Snapping(snap_data) => {
                 self.get_drag_end_snap_state(snap_data, draggable_grab_data)
             }
+            DragEndings::Cancelling(_) => self.get_drag_end_cancel_state(draggable_grab_data, rect),
         };
         tracing::info!("data on drag end: {:?} {:?}", self.id, self.drag_state);
     }
 
     fn get_drag_end_release_state(
         &self,
-        pointer_position: Point2D<f64, f64>,
+        pointer_position: Point2D<f64, AreaSpace>,
         draggable_grab_data: DraggableGrabData,
-        rect: Rect<f64, f64>,
+        rect: Rect<f64, AreaSpace>,
     ) -> DraggableStates {
         let x = pointer_position.x - draggable_grab_data.grab_point.x;
         let y = pointer_position.y - draggable_grab_data.grab_point.y;
-        let resting_position: Point2D<f64, f64> = Point2D::new(x, y);
+        let resting_position: Point2D<f64, AreaSpace> = Point2D::new(x, y);
         let resting_size = rect.size;
         DraggableStates::Resting(DraggableRestStates::Released(Rect::new(
             resting_position,
@@ -227,6 +339,26 @@ impl LocalDragState {
         ))
     }
 
+    fn get_drag_end_cancel_state(
+        &self,
+        draggable_grab_data: DraggableGrabData,
+        rect: Rect<f64, AreaSpace>,
+    ) -> DraggableStates {
+        match draggable_grab_data.drag_origin {
+            DragOrigin::Free(origin_rect) => {
+                DraggableStates::Resting(DraggableRestStates::Released(origin_rect))
+            }
+            DragOrigin::Snapped(og_snap) => DraggableStates::Resting(DraggableRestStates::Snapped(
+                DraggableSnapStates::Transitioning(DraggableTransitionData::new(
+                    SnapInfo::new(None, rect),
+                    og_snap,
+                    DraggableTransitionMode::Resting,
+                    self.id.clone(),
+                )),
+            )),
+        }
+    }
+
     fn update_state_on_other_drag_end(
         &mut self,
         snap_state: DraggableSnapStates,
@@ -240,9 +372,10 @@ impl LocalDragState {
                 true => DraggableSnapStates::Final(preview_data.to),
                 false => DraggableSnapStates::Transitioning(preview_data.reverse()),
             },
-            (DraggableSnapStates::Preview(preview_data), DragEndings::Releasing(_)) => {
-                DraggableSnapStates::Transitioning(preview_data.reverse())
-            }
+            (
+                DraggableSnapStates::Preview(preview_data),
+                DragEndings::Releasing(_) | DragEndings::Cancelling(_),
+            ) => DraggableSnapStates::Transitioning(preview_data.reverse()),
             (DraggableSnapStates::Transitioning(transition), _) => {
                 let res = self.get_transition_end_state(transition);
                 match res {
@@ -268,7 +401,7 @@ impl LocalDragState {
         &mut self,
         draggable_rest_state: DraggableRestStates,
         drag_area_dragging_state: DragAreaActiveDragData,
-        rect: Rect<f64, f64>,
+        rect: Rect<f64, AreaSpace>,
     ) {
         let this_rect = match draggable_rest_state.clone() {
             DraggableRestStates::Released(rect) => rect,
@@ -278,8 +411,9 @@ impl LocalDragState {
                 DraggableSnapStates::Transitioning(_) => rect,
             },
         };
-        let intersects_this_rect =
-            this_rect.contains(drag_area_dragging_state.current_pos.cast_unit());
+        // a panel joining this target as a tab does not push it out of the way
+        let intersects_this_rect = this_rect.contains(drag_area_dragging_state.current_pos)
+            && drag_area_dragging_state.drop_mode == DropMode::Swap;
         match (draggable_rest_state.clone(), intersects_this_rect) {
             (DraggableRestStates::Released(_), _) => (), // no action
             (DraggableRestStates::Snapped(snap_state), _) => {
@@ -315,7 +449,7 @@ impl LocalDragState {
                 if !transition
                     .from
                     .rect
-                    .contains(drag_area_dragging_state.current_pos.cast_unit()) =>
+                    .contains(drag_area_dragging_state.current_pos) =>
             {
                 DraggableSnapStates::Transitioning(transition.reverse())
             }
@@ -332,11 +466,14 @@ impl LocalDragState {
     pub fn get_render_data(
         &self,
         global_drag_state: DragAreaStates,
-        rect: Rect<f64, f64>,
+        rect: Rect<f64, AreaSpace>,
     ) -> DraggableRenderData {
         tracing::info!("getting render data");
         match (self.drag_state.clone(), global_drag_state.clone()) {
             (DraggableStates::Initial, _) => DraggableRenderData::default(),
+            (DraggableStates::Pending(grab_data), _) => self.get_render_data_for_resting_states(
+                Self::get_origin_rest_state(grab_data.drag_origin),
+            ),
             (DraggableStates::Grabbed(grab_data), DragAreaStates::Dragging(drag_data)) => {
                 let origin =
                     Self::origin_with_grab_offset(grab_data.grab_point, drag_data.current_pos);
@@ -353,12 +490,20 @@ impl LocalDragState {
             }
             (
                 DraggableStates::Grabbed(_grab_data),
-                DragAreaStates::Released(DragEndings::Snapping(_to)),
+                DragAreaStates::Released(DragEndings::Snapping(_) | DragEndings::Cancelling(_)),
             ) => DraggableRenderData::free_or_dragging(rect),
             (
                 DraggableStates::Resting(DraggableRestStates::Snapped(snap_state)),
                 DragAreaStates::Dragging(_),
             ) => self.get_render_data_for_avoidance_states(snap_state),
+            // a cancelled drag of a free panel animates back to where it was picked up
+            (
+                DraggableStates::Resting(DraggableRestStates::Released(release_rect)),
+                DragAreaStates::Released(DragEndings::Cancelling(_)),
+            ) if rect.origin != release_rect.origin => DraggableRenderData::returning(Rect::new(
+                release_rect.origin,
+                Self::get_grabbed_size(),
+            )),
             (DraggableStates::Resting(rest_state), _) => {
                 self.get_render_data_for_resting_states(rest_state)
             }
@@ -398,22 +543,30 @@ impl LocalDragState {
             DraggableSnapStates::Preview(transition) => {
                 DraggableRenderData::snapped(transition.to.rect)
             }
+            DraggableSnapStates::Transitioning(transition)
+                if transition.mode == DraggableTransitionMode::Releasing =>
+            {
+                DraggableRenderData::returning(Rect::new(
+                    transition.to.rect.origin,
+                    Self::get_grabbed_size(),
+                ))
+            }
             DraggableSnapStates::Transitioning(transition) => {
-                DraggableRenderData::transitioning(transition.anim)
+                DraggableRenderData::transitioning(transition.to.rect)
             }
         }
     }
 
     fn origin_with_grab_offset(
         drag_point: Point2D<f64, ElementSpace>,
-        pointer_pos: Point2D<f64, f64>,
-    ) -> Point2D<f64, f64> {
+        pointer_pos: Point2D<f64, AreaSpace>,
+    ) -> Point2D<f64, AreaSpace> {
         let x = pointer_pos.x - drag_point.x;
         let y = pointer_pos.y - drag_point.y;
         Point2D::new(x, y)
     }
 
-    fn get_grabbed_size() -> Size2D<f64, f64> {
+    fn get_grabbed_size() -> Size2D<f64, AreaSpace> {
         Size2D::new(200., 200.)
     }
 }
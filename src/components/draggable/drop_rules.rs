@@ -0,0 +1,127 @@
+use core::fmt;
+use std::fmt::{Debug, Display, Formatter};
+use std::rc::Rc;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PanelTraits {
+    pub panel_id: String,
+    pub group: Option<String>,
+    pub tags: Vec<String>,
+}
+
+impl PanelTraits {
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|own| own == tag)
+    }
+}
+
+/// Filters built with `groups` or `tags` compare by their lists, and filters
+/// built with `new` only equal their own clones.
+#[derive(Clone)]
+pub struct DropFilter {
+    accepts: Rc<dyn Fn(&PanelTraits) -> bool>,
+    kind: FilterKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FilterKind {
+    Groups(Vec<String>),
+    Tags(Vec<String>),
+    Closure,
+}
+
+impl DropFilter {
+    pub fn new(accepts: impl Fn(&PanelTraits) -> bool + 'static) -> Self {
+        Self {
+            accepts: Rc::new(accepts),
+            kind: FilterKind::Closure,
+        }
+    }
+
+    pub fn groups(groups: Vec<String>) -> Self {
+        let kind = FilterKind::Groups(groups.clone());
+        Self {
+            accepts: Rc::new(move |panel: &PanelTraits| {
+                panel
+                    .group
+                    .as_ref()
+                    .is_some_and(|group| groups.contains(group))
+            }),
+            kind,
+        }
+    }
+
+    pub fn tags(tags: Vec<String>) -> Self {
+        let kind = FilterKind::Tags(tags.clone());
+        Self {
+            accepts: Rc::new(move |panel: &PanelTraits| tags.iter().any(|tag| panel.has_tag(tag))),
+            kind,
+        }
+    }
+
+    pub fn accepts(&self, panel: &PanelTraits) -> bool {
+        (self.accepts)(panel)
+    }
+}
+
+impl PartialEq for DropFilter {
+    fn eq(&self, other: &Self) -> bool {
+        match (&self.kind, &other.kind) {
+            (FilterKind::Closure, _) | (_, FilterKind::Closure) => {
+                Rc::ptr_eq(&self.accepts, &other.accepts)
+            }
+            (kind, other_kind) => kind == other_kind,
+        }
+    }
+}
+
+impl Debug for DropFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "DropFilter")
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TargetRules {
+    pub accepts: Option<DropFilter>,
+    pub capacity: Option<usize>,
+    pub tabs: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DropRejection {
+    pub panel_id: String,
+    pub target_id: String,
+    pub reason: RejectionReason,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RejectionReason {
+    /// The target's DragArea does not take panels from the panel's DragArea.
+    Area,
+    Filter,
+    /// The drop would put more panels in the target than its capacity.
+    Capacity(usize),
+}
+
+impl Display for DropRejection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            RejectionReason::Area => write!(
+                f,
+                "Target \"{}\" does not accept panels from the DragArea of \"{}\"",
+                self.target_id, self.panel_id
+            ),
+            RejectionReason::Filter => write!(
+                f,
+                "Target \"{}\" does not accept \"{}\"",
+                self.target_id, self.panel_id
+            ),
+            RejectionReason::Capacity(capacity) => write!(
+                f,
+                "Target \"{}\" is full ({} panels), cannot take \"{}\"",
+                self.target_id, capacity, self.panel_id
+            ),
+        }
+    }
+}
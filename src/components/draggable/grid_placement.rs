@@ -0,0 +1,213 @@
+use core::fmt;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GridPlacement {
+    /// Spans `column_span` columns from column line `column`. Without a
+    /// `row`, the Cell takes `row_span` rows on the next free row.
+    Lines {
+        column: u64,
+        column_span: u64,
+        row: Option<u64>,
+        row_span: u64,
+    },
+    /// Takes a named area of the DragArea's `grid_areas`.
+    Area(String),
+}
+
+impl GridPlacement {
+    pub fn columns(column: u64, column_span: u64) -> Self {
+        Self::Lines {
+            column,
+            column_span,
+            row: None,
+            row_span: 1,
+        }
+    }
+
+    /// Pins lines placement to `row_span` rows from row line `row`.
+    pub fn with_rows(self, row: u64, row_span: u64) -> Self {
+        match self {
+            Self::Lines {
+                column,
+                column_span,
+                ..
+            } => Self::Lines {
+                column,
+                column_span,
+                row: Some(row),
+                row_span,
+            },
+            area => area,
+        }
+    }
+
+    pub fn area(name: String) -> Self {
+        Self::Area(name)
+    }
+
+    /// Outside a DragArea's grid, named areas are left to the enclosing grid.
+    pub fn get_style(&self) -> String {
+        match self {
+            Self::Lines { .. } => self
+                .get_tracks()
+                .map(|tracks| tracks.get_style())
+                .unwrap_or_default(),
+            Self::Area(name) => format!("grid-area: {};", name),
+        }
+    }
+
+    pub fn get_tracks(&self) -> Option<GridTracks> {
+        match self {
+            Self::Lines {
+                column,
+                column_span,
+                row,
+                row_span,
+            } => Some(GridTracks {
+                column: *column,
+                column_span: *column_span,
+                row: *row,
+                row_span: *row_span,
+            }),
+            Self::Area(_) => None,
+        }
+    }
+}
+
+impl Display for GridPlacement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Lines {
+                column,
+                column_span,
+                row: Some(row),
+                row_span,
+            } => write!(
+                f,
+                "columns {}/span {}, rows {}/span {}",
+                column, column_span, row, row_span
+            ),
+            Self::Lines {
+                column,
+                column_span,
+                ..
+            } => write!(f, "columns {}/span {}", column, column_span),
+            Self::Area(name) => write!(f, "area \"{}\"", name),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridTracks {
+    pub column: u64,
+    pub column_span: u64,
+    pub row: Option<u64>,
+    pub row_span: u64,
+}
+
+impl GridTracks {
+    pub fn get_style(&self) -> String {
+        let row = match self.row {
+            Some(row) => format!("{}/span {}", row, self.row_span),
+            None => format!("span {}", self.row_span),
+        };
+        format!(
+            "grid-column: {}/span {}; grid-row: {};",
+            self.column, self.column_span, row
+        )
+    }
+
+    pub fn get_column_end(&self) -> u64 {
+        self.column + self.column_span
+    }
+}
+
+/// CSS drops the whole template when an area is not a filled rectangle, so
+/// such an area's name is returned as the error.
+pub(crate) fn get_named_areas(areas: &[String]) -> Result<BTreeMap<String, GridTracks>, String> {
+    let mut named: BTreeMap<String, (u64, u64, u64, u64)> = BTreeMap::new();
+    let mut cells: BTreeMap<String, u64> = BTreeMap::new();
+    for (row, line) in areas.iter().enumerate() {
+        for (column, name) in line.split_whitespace().enumerate() {
+            if name.chars().all(|c| c == '.') {
+                continue;
+            }
+            let (row, column) = (row as u64 + 1, column as u64 + 1);
+            *cells.entry(name.to_string()).or_default() += 1;
+            let bounds = named
+                .entry(name.to_string())
+                .or_insert((column, column, row, row));
+            *bounds = (
+                bounds.0.min(column),
+                bounds.1.max(column),
+                bounds.2.min(row),
+                bounds.3.max(row),
+            );
+        }
+    }
+    named
+        .into_iter()
+        .map(|(name, (first_column, last_column, first_row, last_row))| {
+            let tracks = GridTracks {
+                column: first_column,
+                column_span: last_column - first_column + 1,
+                row: Some(first_row),
+                row_span: last_row - first_row + 1,
+            };
+            match cells.get(&name) == Some(&(tracks.column_span * tracks.row_span)) {
+                true => Ok((name, tracks)),
+                false => Err(name),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn named_areas_span_their_rows_and_columns() {
+        let named = get_named_areas(&areas(&["head head", "side main", "side main"])).unwrap();
+        assert_eq!(
+            named.get("head"),
+            Some(&GridTracks {
+                column: 1,
+                column_span: 2,
+                row: Some(1),
+                row_span: 1,
+            })
+        );
+        assert_eq!(
+            named.get("side"),
+            Some(&GridTracks {
+                column: 1,
+                column_span: 1,
+                row: Some(2),
+                row_span: 2,
+            })
+        );
+        assert_eq!(named.len(), 3);
+    }
+
+    #[test]
+    fn named_areas_skip_unnamed_cells() {
+        let named = get_named_areas(&areas(&["a .", "... b"])).unwrap();
+        assert_eq!(named.keys().collect::<Vec<_>>(), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn named_areas_reject_shapes_that_are_not_rectangles() {
+        assert_eq!(
+            get_named_areas(&areas(&["a a", "a b"])),
+            Err("a".to_string())
+        );
+        assert_eq!(get_named_areas(&areas(&["a b a"])), Err("a".to_string()));
+    }
+}
@@ -0,0 +1,89 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+/// Keeps `keys`, and `held_keys` while the element is `aria-grabbed`, from
+/// scrolling the page. Keys pressed on its children are left alone.
+pub(crate) fn use_key_guard(
+    element: Signal<Option<Rc<MountedData>>>,
+    keys: &'static [&'static str],
+    held_keys: &'static [&'static str],
+) {
+    let mut guard = use_signal(|| None as Option<KeyGuard>);
+    use_effect(move || {
+        if let Some(element) = element.read().as_ref() {
+            guard.set(KeyGuard::new(element, keys, held_keys));
+        }
+    });
+    use_drop(move || {
+        if let Ok(mut guard) = guard.try_write() {
+            guard.take();
+        }
+    });
+}
+
+#[cfg(target_arch = "wasm32")]
+struct KeyGuard {
+    element: web_sys::Element,
+    callback: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::KeyboardEvent)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl KeyGuard {
+    fn new(
+        element: &MountedData,
+        keys: &'static [&'static str],
+        held_keys: &'static [&'static str],
+    ) -> Option<Self> {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let element = element.downcast::<web_sys::Element>()?.clone();
+        let guarded = element.clone();
+        let callback = Closure::<dyn FnMut(web_sys::KeyboardEvent)>::new(
+            move |event: web_sys::KeyboardEvent| {
+                if event.target().as_ref() != Some(guarded.as_ref()) {
+                    return;
+                }
+                let key = event.key();
+                let holding = guarded.get_attribute("aria-grabbed").as_deref() == Some("true");
+                if keys.contains(&key.as_str()) || (holding && held_keys.contains(&key.as_str())) {
+                    event.prevent_default();
+                }
+            },
+        );
+        if let Err(err) =
+            element.add_event_listener_with_callback("keydown", callback.as_ref().unchecked_ref())
+        {
+            tracing::error!("could not guard keys: {:?}", err);
+            return None;
+        }
+        Some(Self { element, callback })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for KeyGuard {
+    fn drop(&mut self) {
+        use wasm_bindgen::JsCast;
+
+        let _ = self
+            .element
+            .remove_event_listener_with_callback("keydown", self.callback.as_ref().unchecked_ref());
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct KeyGuard;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl KeyGuard {
+    fn new(
+        element: &MountedData,
+        keys: &'static [&'static str],
+        held_keys: &'static [&'static str],
+    ) -> Option<Self> {
+        let _ = (element, keys, held_keys);
+        tracing::info!("keys are only guarded on web");
+        None
+    }
+}
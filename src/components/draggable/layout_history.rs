@@ -0,0 +1,310 @@
+use super::{AreaSpace, DragOrigin, LayoutState, PanelPlacement, SnapInfo, TabStack, TargetSplit};
+use dioxus::prelude::*;
+use dioxus_elements::geometry::euclid::Rect;
+
+const MAX_HISTORY_LENGTH: usize = 100;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LayoutOperation {
+    pub panel_id: String,
+    pub source: DragOrigin,
+    pub destination: SnapInfo,
+    pub displaced: Vec<DisplacedPanel>,
+    /// The panel joined the destination as a tab instead of swapping.
+    pub stacked: bool,
+    pub split: Option<TargetSplit>,
+    /// The stack the panel left, as it was before the drop dissolved it.
+    pub source_stack: Option<(String, TabStack)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisplacedPanel {
+    pub panel_id: String,
+    pub from: SnapInfo,
+    pub to: SnapInfo,
+}
+
+impl LayoutOperation {
+    /// Every panel resting in the destination is displaced into the dragged panel's origin.
+    pub fn from_drop(
+        panel_id: String,
+        source: DragOrigin,
+        destination: SnapInfo,
+        layout: &LayoutState,
+    ) -> Option<Self> {
+        let destination_id = destination.target_id.clone()?;
+        if source.get_snap_info().target_id.as_ref() == Some(&destination_id) {
+            return None;
+        }
+        let displaced = layout
+            .get_panels_in_target(&destination_id)
+            .into_iter()
+            .filter(|displaced_id| *displaced_id != panel_id)
+            .map(|displaced_id| DisplacedPanel {
+                panel_id: displaced_id,
+                from: destination.clone(),
+                to: source.get_snap_info(),
+            })
+            .collect();
+        Some(Self {
+            panel_id,
+            source,
+            destination,
+            displaced,
+            stacked: false,
+            split: None,
+            source_stack: None,
+        })
+    }
+
+    pub fn from_stack(panel_id: String, source: DragOrigin, destination: SnapInfo) -> Option<Self> {
+        let destination_id = destination.target_id.clone()?;
+        if source.get_snap_info().target_id.as_ref() == Some(&destination_id) {
+            return None;
+        }
+        Some(Self {
+            panel_id,
+            source,
+            destination,
+            displaced: Vec::new(),
+            stacked: true,
+            split: None,
+            source_stack: None,
+        })
+    }
+
+    pub fn from_split(
+        panel_id: String,
+        source: DragOrigin,
+        destination: SnapInfo,
+        split: TargetSplit,
+    ) -> Self {
+        Self {
+            panel_id,
+            source,
+            destination,
+            displaced: Vec::new(),
+            stacked: false,
+            split: Some(split),
+            source_stack: None,
+        }
+    }
+
+    /// A drop outside any target, leaving the panel free at `rect`.
+    pub fn from_release(
+        panel_id: String,
+        source: DragOrigin,
+        rect: Rect<f64, AreaSpace>,
+    ) -> Option<Self> {
+        if source == DragOrigin::Free(rect) {
+            return None;
+        }
+        Some(Self {
+            panel_id,
+            source,
+            destination: SnapInfo::new(None, rect),
+            displaced: Vec::new(),
+            stacked: false,
+            split: None,
+            source_stack: None,
+        })
+    }
+
+    pub fn with_source_stack(mut self, layout: &LayoutState) -> Self {
+        self.source_stack = self
+            .source
+            .get_snap_info()
+            .target_id
+            .and_then(|target_id| Some((target_id.clone(), layout.get_tab_stack(&target_id)?)));
+        self
+    }
+
+    pub fn undo_placements(&self) -> Vec<(String, PanelPlacement)> {
+        let mut placements = vec![(self.panel_id.clone(), self.source.clone().into())];
+        for displaced in &self.displaced {
+            placements.push((displaced.panel_id.clone(), displaced.from.clone().into()));
+        }
+        placements
+    }
+
+    pub fn redo_placements(&self) -> Vec<(String, PanelPlacement)> {
+        let destination = match self.destination.target_id {
+            Some(_) => self.destination.clone().into(),
+            None => PanelPlacement::Released {
+                rect: self.destination.rect.into(),
+            },
+        };
+        let mut placements = vec![(self.panel_id.clone(), destination)];
+        for displaced in &self.displaced {
+            placements.push((displaced.panel_id.clone(), displaced.to.clone().into()));
+        }
+        placements
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutHistory {
+    undo_stack: Vec<LayoutOperation>,
+    redo_stack: Vec<LayoutOperation>,
+}
+
+impl LayoutHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&mut self, operation: LayoutOperation) {
+        tracing::info!("recording operation: {:?}", operation);
+        self.undo_stack.push(operation);
+        if self.undo_stack.len() > MAX_HISTORY_LENGTH {
+            self.undo_stack.remove(0);
+        }
+        self.redo_stack.clear();
+    }
+
+    pub fn undo(&mut self) -> Option<LayoutOperation> {
+        let operation = self.undo_stack.pop()?;
+        self.redo_stack.push(operation.clone());
+        Some(operation)
+    }
+
+    pub fn redo(&mut self) -> Option<LayoutOperation> {
+        let operation = self.redo_stack.pop()?;
+        self.undo_stack.push(operation.clone());
+        Some(operation)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::draggable::Edge;
+    use dioxus_elements::geometry::euclid::{Point2D, Size2D};
+
+    fn rect(x: f64) -> Rect<f64, AreaSpace> {
+        Rect::new(Point2D::new(x, 0.), Size2D::new(100., 100.))
+    }
+
+    fn snapped(target_id: &str, x: f64) -> SnapInfo {
+        SnapInfo::new(Some(target_id.to_string()), rect(x))
+    }
+
+    fn release(panel_id: &str, x: f64) -> LayoutOperation {
+        LayoutOperation::from_release(panel_id.to_string(), DragOrigin::Free(rect(-1.)), rect(x))
+            .unwrap()
+    }
+
+    #[test]
+    fn history_keeps_the_last_hundred_operations() {
+        let mut history = LayoutHistory::new();
+        for x in 0..=MAX_HISTORY_LENGTH {
+            history.record(release("a", x as f64));
+        }
+        let mut undone = Vec::new();
+        while let Some(operation) = history.undo() {
+            undone.push(operation);
+        }
+        assert_eq!(undone.len(), MAX_HISTORY_LENGTH);
+        assert_eq!(undone.last(), Some(&release("a", 1.)));
+    }
+
+    #[test]
+    fn a_new_operation_after_undo_clears_redo() {
+        let mut history = LayoutHistory::new();
+        history.record(release("a", 0.));
+        history.record(release("a", 1.));
+        assert_eq!(history.undo(), Some(release("a", 1.)));
+        assert!(history.can_redo());
+        history.record(release("b", 2.));
+        assert!(!history.can_redo());
+        assert_eq!(history.redo(), None);
+        assert_eq!(history.undo(), Some(release("b", 2.)));
+        assert_eq!(history.undo(), Some(release("a", 0.)));
+    }
+
+    #[test]
+    fn drops_displace_the_panels_in_the_destination() {
+        let mut layout = LayoutState::new();
+        layout.record_panel("b".to_string(), PanelPlacement::from(snapped("two", 100.)));
+        let source = DragOrigin::Snapped(snapped("one", 0.));
+        let operation =
+            LayoutOperation::from_drop("a".into(), source.clone(), snapped("two", 100.), &layout)
+                .unwrap();
+        assert_eq!(
+            operation.displaced,
+            vec![DisplacedPanel {
+                panel_id: "b".into(),
+                from: snapped("two", 100.),
+                to: snapped("one", 0.),
+            }]
+        );
+        assert_eq!(
+            operation.undo_placements(),
+            vec![
+                ("a".to_string(), PanelPlacement::from(snapped("one", 0.))),
+                ("b".to_string(), PanelPlacement::from(snapped("two", 100.))),
+            ]
+        );
+        assert_eq!(
+            operation.redo_placements(),
+            vec![
+                ("a".to_string(), PanelPlacement::from(snapped("two", 100.))),
+                ("b".to_string(), PanelPlacement::from(snapped("one", 0.))),
+            ]
+        );
+        assert!(
+            LayoutOperation::from_drop("a".into(), source, snapped("one", 0.), &layout).is_none()
+        );
+    }
+
+    #[test]
+    fn stacking_displaces_nothing() {
+        let source = DragOrigin::Snapped(snapped("one", 0.));
+        let operation =
+            LayoutOperation::from_stack("a".into(), source.clone(), snapped("two", 100.)).unwrap();
+        assert!(operation.stacked);
+        assert!(operation.displaced.is_empty());
+        assert!(LayoutOperation::from_stack("a".into(), source, snapped("one", 0.)).is_none());
+    }
+
+    #[test]
+    fn splits_keep_the_split_for_undo() {
+        let split = TargetSplit::new("two".into(), Edge::Left);
+        let operation = LayoutOperation::from_split(
+            "a".into(),
+            DragOrigin::Snapped(snapped("one", 0.)),
+            snapped(&split.target_id, 100.),
+            split.clone(),
+        );
+        assert_eq!(operation.split, Some(split));
+        assert!(!operation.stacked);
+    }
+
+    #[test]
+    fn releases_redo_as_free_placements() {
+        let source = DragOrigin::Snapped(snapped("one", 0.));
+        let operation = LayoutOperation::from_release("a".into(), source, rect(50.)).unwrap();
+        assert_eq!(operation.destination.target_id, None);
+        assert_eq!(
+            operation.redo_placements(),
+            vec![(
+                "a".to_string(),
+                PanelPlacement::Released {
+                    rect: rect(50.).into()
+                }
+            )]
+        );
+        assert!(
+            LayoutOperation::from_release("a".into(), DragOrigin::Free(rect(50.)), rect(50.))
+                .is_none()
+        );
+    }
+}
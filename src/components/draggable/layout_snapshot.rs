@@ -0,0 +1,357 @@
+use std::collections::BTreeMap;
+
+use super::{AreaSpace, DragOrigin, SnapInfo};
+use dioxus::prelude::*;
+use dioxus_elements::geometry::euclid::{Point2D, Rect, Size2D};
+
+/// A saved arrangement of every Draggable in a DragArea, keyed by Draggable id.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutSnapshot {
+    pub panels: BTreeMap<String, PanelPlacement>,
+    /// Tab stacks keyed by DragTarget id.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tabs: BTreeMap<String, TabStack>,
+    /// Split DragTargets keyed by the id of the target that was split.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub splits: BTreeMap<String, TargetSplit>,
+    /// Track sizes keyed by DragArea id.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub sizes: BTreeMap<String, GridSizes>,
+    /// Arrangements kept per breakpoint, keyed by DragArea id.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub breakpoints: BTreeMap<String, BreakpointLayouts>,
+}
+
+impl LayoutSnapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.panels.is_empty()
+            && self.tabs.is_empty()
+            && self.splits.is_empty()
+            && self.sizes.is_empty()
+            && self.breakpoints.is_empty()
+    }
+
+    pub fn get_placement(&self, panel_id: &str) -> Option<&PanelPlacement> {
+        self.panels.get(panel_id)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TabStack {
+    pub panels: Vec<String>,
+    pub active: String,
+}
+
+impl TabStack {
+    /// Adds or moves a panel to a tab position and makes it the active tab.
+    pub fn insert(&mut self, panel_id: String, index: usize) {
+        self.panels.retain(|id| *id != panel_id);
+        let index = index.min(self.panels.len());
+        self.panels.insert(index, panel_id.clone());
+        self.active = panel_id;
+    }
+
+    pub fn remove(&mut self, panel_id: &str) {
+        let index = match self.panels.iter().position(|id| id == panel_id) {
+            Some(index) => index,
+            None => return,
+        };
+        self.panels.remove(index);
+        if self.active == panel_id {
+            let neighbour = index.min(self.panels.len().saturating_sub(1));
+            self.active = self.panels.get(neighbour).cloned().unwrap_or_default();
+        }
+    }
+
+    pub fn contains(&self, panel_id: &str) -> bool {
+        self.panels.iter().any(|id| id == panel_id)
+    }
+}
+
+/// Track sizes as CSS `fr` fractions.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GridSizes {
+    pub columns: Vec<f64>,
+    /// Row sizes keyed by Cell id.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rows: BTreeMap<String, Vec<f64>>,
+    /// Column sizes kept for the area's other breakpoints, keyed by column count.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub breakpoint_columns: BTreeMap<u8, Vec<f64>>,
+}
+
+/// The arrangements a DragArea keeps for each of its breakpoints.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BreakpointLayouts {
+    pub active: Option<String>,
+    /// Keyed by breakpoint name, then by Draggable id.
+    pub panels: BTreeMap<String, BTreeMap<String, PanelPlacement>>,
+}
+
+/// A DragTarget divided along one edge to make room for a new target.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TargetSplit {
+    pub parent_id: String,
+    pub edge: Edge,
+    pub target_id: String,
+}
+
+impl TargetSplit {
+    pub fn new(parent_id: String, edge: Edge) -> Self {
+        Self {
+            target_id: format!("{}:{}", parent_id, edge.get_name()),
+            parent_id,
+            edge,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl Edge {
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Top => "top",
+            Self::Bottom => "bottom",
+        }
+    }
+
+    /// Each edge's zone covers `fraction` of the rect's width or height.
+    pub fn from_point(
+        rect: Rect<f64, AreaSpace>,
+        point: Point2D<f64, AreaSpace>,
+        fraction: f64,
+    ) -> Option<Self> {
+        let x = (point.x - rect.origin.x) / rect.width();
+        let y = (point.y - rect.origin.y) / rect.height();
+        let distances = [
+            (x, Self::Left),
+            (1. - x, Self::Right),
+            (y, Self::Top),
+            (1. - y, Self::Bottom),
+        ];
+        distances
+            .into_iter()
+            .filter(|(distance, _)| *distance < fraction)
+            .min_by(|(a, _), (b, _)| a.total_cmp(b))
+            .map(|(_, edge)| edge)
+    }
+
+    pub fn split_rect(&self, rect: Rect<f64, AreaSpace>) -> Rect<f64, AreaSpace> {
+        let half = Size2D::new(rect.width() / 2., rect.height() / 2.);
+        match self {
+            Self::Left => Rect::new(rect.origin, Size2D::new(half.width, rect.height())),
+            Self::Right => Rect::new(
+                Point2D::new(rect.origin.x + half.width, rect.origin.y),
+                Size2D::new(half.width, rect.height()),
+            ),
+            Self::Top => Rect::new(rect.origin, Size2D::new(rect.width(), half.height)),
+            Self::Bottom => Rect::new(
+                Point2D::new(rect.origin.x, rect.origin.y + half.height),
+                Size2D::new(rect.width(), half.height),
+            ),
+        }
+    }
+
+    /// True when the split-off target comes before the original in the layout.
+    pub fn is_leading(&self) -> bool {
+        matches!(self, Self::Left | Self::Top)
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        matches!(self, Self::Left | Self::Right)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TabPosition {
+    pub index: usize,
+    pub count: usize,
+    pub active: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PanelPlacement {
+    Snapped {
+        target_id: Option<String>,
+        rect: LayoutRect,
+    },
+    Released {
+        rect: LayoutRect,
+    },
+}
+
+impl From<SnapInfo> for PanelPlacement {
+    fn from(snap_info: SnapInfo) -> Self {
+        Self::Snapped {
+            target_id: snap_info.target_id,
+            rect: snap_info.rect.into(),
+        }
+    }
+}
+
+impl From<DragOrigin> for PanelPlacement {
+    fn from(origin: DragOrigin) -> Self {
+        match origin {
+            DragOrigin::Snapped(snap_info) => snap_info.into(),
+            DragOrigin::Free(rect) => Self::Released { rect: rect.into() },
+        }
+    }
+}
+
+/// Plain rect used in snapshots so they do not depend on euclid's serde support.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LayoutRect {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl From<Rect<f64, AreaSpace>> for LayoutRect {
+    fn from(rect: Rect<f64, AreaSpace>) -> Self {
+        Self {
+            x: rect.origin.x,
+            y: rect.origin.y,
+            width: rect.width(),
+            height: rect.height(),
+        }
+    }
+}
+
+impl From<LayoutRect> for Rect<f64, AreaSpace> {
+    fn from(rect: LayoutRect) -> Self {
+        Rect::new(
+            Point2D::new(rect.x, rect.y),
+            Size2D::new(rect.width, rect.height),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f64, y: f64, width: f64, height: f64) -> Rect<f64, AreaSpace> {
+        Rect::new(Point2D::new(x, y), Size2D::new(width, height))
+    }
+
+    fn stack(panels: &[&str], active: &str) -> TabStack {
+        TabStack {
+            panels: panels.iter().map(|id| id.to_string()).collect(),
+            active: active.to_string(),
+        }
+    }
+
+    #[test]
+    fn inserted_tabs_become_active() {
+        let mut tabs = stack(&["a", "b"], "a");
+        tabs.insert("c".into(), 1);
+        assert_eq!(tabs, stack(&["a", "c", "b"], "c"));
+        // past the end appends
+        tabs.insert("d".into(), 10);
+        assert_eq!(tabs, stack(&["a", "c", "b", "d"], "d"));
+    }
+
+    #[test]
+    fn inserting_a_present_tab_moves_it() {
+        let mut tabs = stack(&["a", "b", "c"], "a");
+        tabs.insert("a".into(), 2);
+        assert_eq!(tabs, stack(&["b", "c", "a"], "a"));
+    }
+
+    #[test]
+    fn removing_the_active_tab_activates_the_next_one() {
+        let mut tabs = stack(&["a", "b", "c"], "b");
+        tabs.remove("b");
+        assert_eq!(tabs, stack(&["a", "c"], "c"));
+        // the last tab falls back to the one before it
+        tabs.remove("c");
+        assert_eq!(tabs, stack(&["a"], "a"));
+        tabs.remove("a");
+        assert_eq!(tabs, stack(&[], ""));
+    }
+
+    #[test]
+    fn removing_another_tab_keeps_the_active_one() {
+        let mut tabs = stack(&["a", "b", "c"], "c");
+        tabs.remove("a");
+        assert_eq!(tabs, stack(&["b", "c"], "c"));
+        tabs.remove("x");
+        assert_eq!(tabs, stack(&["b", "c"], "c"));
+        assert!(!tabs.contains("a"));
+    }
+
+    #[test]
+    fn points_in_an_edge_zone_pick_that_edge() {
+        let target = rect(100., 100., 200., 100.);
+        let edge = |x, y| Edge::from_point(target, Point2D::new(x, y), 0.25);
+        assert_eq!(edge(110., 150.), Some(Edge::Left));
+        assert_eq!(edge(290., 150.), Some(Edge::Right));
+        assert_eq!(edge(200., 110.), Some(Edge::Top));
+        assert_eq!(edge(200., 190.), Some(Edge::Bottom));
+    }
+
+    #[test]
+    fn points_in_the_middle_pick_no_edge() {
+        let target = rect(100., 100., 200., 100.);
+        assert_eq!(
+            Edge::from_point(target, Point2D::new(200., 150.), 0.25),
+            None
+        );
+        // a zone is a share of the target's width or height, not a fixed distance
+        assert_eq!(
+            Edge::from_point(target, Point2D::new(155., 150.), 0.25),
+            None
+        );
+    }
+
+    #[test]
+    fn corners_pick_the_nearer_edge() {
+        let target = rect(0., 0., 100., 100.);
+        assert_eq!(
+            Edge::from_point(target, Point2D::new(5., 10.), 0.25),
+            Some(Edge::Left)
+        );
+        assert_eq!(
+            Edge::from_point(target, Point2D::new(90., 95.), 0.25),
+            Some(Edge::Bottom)
+        );
+    }
+
+    #[test]
+    fn splits_take_half_of_the_target_on_their_edge() {
+        let target = rect(10., 20., 200., 100.);
+        assert_eq!(Edge::Left.split_rect(target), rect(10., 20., 100., 100.));
+        assert_eq!(Edge::Right.split_rect(target), rect(110., 20., 100., 100.));
+        assert_eq!(Edge::Top.split_rect(target), rect(10., 20., 200., 50.));
+        assert_eq!(Edge::Bottom.split_rect(target), rect(10., 70., 200., 50.));
+    }
+
+    #[test]
+    fn split_targets_are_named_after_their_parent_and_edge() {
+        let split = TargetSplit::new("main".into(), Edge::Bottom);
+        assert_eq!(split.target_id, "main:bottom");
+        assert_eq!(split.parent_id, "main");
+    }
+}
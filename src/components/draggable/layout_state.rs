@@ -0,0 +1,1024 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::{
+    AreaSpace, Breakpoint, BreakpointLayouts, DragError, DragErrorType, DragOrigin,
+    DraggableRestStates, DraggableSnapStates, DropMode, DropRejection, GridSizes, LayoutSnapshot,
+    PanelDragEvent, PanelPlacement, PanelTraits, RejectionReason, SnapInfo, TabPosition, TabStack,
+    TargetRules, TargetSplit,
+};
+use dioxus::prelude::*;
+use dioxus_elements::geometry::{
+    euclid::{Point2D, Rect, Vector2D},
+    ClientSpace,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LayoutState {
+    panels: BTreeMap<String, PanelPlacement>,
+    targets: HashMap<String, Rect<f64, AreaSpace>>,
+    pending: BTreeMap<String, PanelPlacement>,
+    unsettled: HashSet<String>,
+    home_targets: HashMap<String, String>,
+    panel_titles: HashMap<String, String>,
+    target_labels: HashMap<String, String>,
+    focus_request: Option<String>,
+    area_sources: HashMap<String, Option<Vec<String>>>,
+    area_origins: BTreeMap<String, Point2D<f64, ClientSpace>>,
+    persistence_areas: BTreeMap<String, Vec<String>>,
+    panel_areas: HashMap<String, String>,
+    target_areas: HashMap<String, String>,
+    panel_traits: HashMap<String, PanelTraits>,
+    target_rules: HashMap<String, TargetRules>,
+    tab_stacks: BTreeMap<String, TabStack>,
+    splits: BTreeMap<String, TargetSplit>,
+    grid_sizes: BTreeMap<String, GridSizes>,
+    breakpoint_layouts: BTreeMap<String, BreakpointLayouts>,
+    errors: Vec<DragError>,
+}
+
+impl LayoutState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn export(&self) -> LayoutSnapshot {
+        LayoutSnapshot {
+            panels: self.panels.clone(),
+            tabs: self.tab_stacks.clone(),
+            splits: self.splits.clone(),
+            sizes: self.grid_sizes.clone(),
+            breakpoints: self.breakpoint_layouts.clone(),
+        }
+    }
+
+    /// The placements owned by one DragArea: panels resting in its targets,
+    /// and its own panels that rest outside every other area's targets.
+    pub fn export_area(&self, area_id: &str) -> LayoutSnapshot {
+        self.export_areas(&[area_id.to_string()])
+    }
+
+    pub fn export_areas(&self, area_ids: &[String]) -> LayoutSnapshot {
+        let owned = |area_id: Option<String>| area_id.is_some_and(|id| area_ids.contains(&id));
+        LayoutSnapshot {
+            panels: self
+                .panels
+                .iter()
+                .filter(|(panel_id, placement)| owned(self.get_placement_area(panel_id, placement)))
+                .map(|(panel_id, placement)| (panel_id.clone(), placement.clone()))
+                .collect(),
+            tabs: self
+                .tab_stacks
+                .iter()
+                .filter(|(target_id, _)| owned(self.get_target_area(target_id)))
+                .map(|(target_id, stack)| (target_id.clone(), stack.clone()))
+                .collect(),
+            splits: self
+                .splits
+                .iter()
+                .filter(|(target_id, _)| owned(self.get_target_area(target_id)))
+                .map(|(target_id, split)| (target_id.clone(), split.clone()))
+                .collect(),
+            sizes: self
+                .grid_sizes
+                .iter()
+                .filter(|(area_id, _)| area_ids.contains(area_id))
+                .map(|(area_id, sizes)| (area_id.clone(), sizes.clone()))
+                .collect(),
+            breakpoints: self
+                .breakpoint_layouts
+                .iter()
+                .filter(|(area_id, _)| area_ids.contains(area_id))
+                .map(|(area_id, layouts)| (area_id.clone(), layouts.clone()))
+                .collect(),
+        }
+    }
+
+    /// Each Draggable applies its placement once its target has been measured.
+    pub fn import(&mut self, snapshot: LayoutSnapshot) {
+        tracing::info!("importing layout: {:?}", snapshot);
+        self.pending = snapshot.panels;
+        self.tab_stacks = snapshot.tabs;
+        self.splits = snapshot.splits;
+        self.grid_sizes = snapshot.sizes;
+        self.breakpoint_layouts = snapshot.breakpoints;
+    }
+
+    /// Replaces only what the area owns, leaving the areas sharing this layout as they are.
+    pub fn import_area(&mut self, area_id: &str, snapshot: LayoutSnapshot) {
+        tracing::info!("importing layout into {}: {:?}", area_id, snapshot);
+        let owned = self.export_area(area_id);
+        self.pending.retain(|panel_id, _| {
+            self.panel_areas.get(panel_id).map(String::as_str) != Some(area_id)
+        });
+        self.tab_stacks
+            .retain(|target_id, _| !owned.tabs.contains_key(target_id));
+        self.splits
+            .retain(|target_id, _| !owned.splits.contains_key(target_id));
+        self.grid_sizes.remove(area_id);
+        self.breakpoint_layouts.remove(area_id);
+        self.pending.extend(snapshot.panels);
+        self.tab_stacks.extend(snapshot.tabs);
+        self.splits.extend(snapshot.splits);
+        self.grid_sizes.extend(snapshot.sizes);
+        self.breakpoint_layouts.extend(snapshot.breakpoints);
+    }
+
+    pub fn report_error(&mut self, error: DragError) {
+        self.errors.push(error);
+    }
+
+    pub fn take_errors(&mut self) -> Vec<DragError> {
+        std::mem::take(&mut self.errors)
+    }
+
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// `accepts_from` of None accepts panels from every area.
+    pub fn register_area(&mut self, area_id: String, accepts_from: Option<Vec<String>>) {
+        self.area_sources.insert(area_id, accepts_from);
+    }
+
+    pub fn remove_area(&mut self, area_id: &str) {
+        self.area_sources.remove(area_id);
+        self.area_origins.remove(area_id);
+        for areas in self.persistence_areas.values_mut() {
+            areas.retain(|id| id != area_id);
+        }
+        self.persistence_areas.retain(|_, areas| !areas.is_empty());
+    }
+
+    /// Areas sharing a persistence name share one saved layout, which only
+    /// the first of them loads and saves. Returns true for that area.
+    pub fn claim_persistence(&mut self, name: String, area_id: String) -> bool {
+        let areas = self.persistence_areas.entry(name).or_default();
+        areas.push(area_id);
+        areas.len() == 1
+    }
+
+    pub fn get_persistence_areas(&self, name: &str) -> Vec<String> {
+        self.persistence_areas
+            .get(name)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub fn record_area_origin(&mut self, area_id: String, origin: Point2D<f64, ClientSpace>) {
+        self.area_origins.insert(area_id, origin);
+    }
+
+    pub fn get_area_origin(&self, area_id: &str) -> Option<Point2D<f64, ClientSpace>> {
+        self.area_origins.get(area_id).copied()
+    }
+
+    /// How far an area sits from the area whose id sorts first, which every
+    /// coordinated area measures from so their rects compare directly.
+    pub fn get_frame_offset(&self, area_id: &str) -> Option<Vector2D<f64, ClientSpace>> {
+        let (_, frame_origin) = self.area_origins.iter().next()?;
+        Some(*self.area_origins.get(area_id)? - *frame_origin)
+    }
+
+    pub fn record_panel_area(&mut self, panel_id: String, area_id: String) {
+        self.panel_areas.insert(panel_id, area_id);
+    }
+
+    pub fn get_panel_area(&self, panel_id: &str) -> Option<String> {
+        self.panel_areas.get(panel_id).cloned()
+    }
+
+    pub fn record_target_area(&mut self, target_id: String, area_id: String) {
+        self.target_areas.insert(target_id, area_id);
+    }
+
+    pub fn get_target_area(&self, target_id: &str) -> Option<String> {
+        self.target_areas.get(target_id).cloned()
+    }
+
+    fn get_placement_area(&self, panel_id: &str, placement: &PanelPlacement) -> Option<String> {
+        match placement {
+            PanelPlacement::Snapped {
+                target_id: Some(target_id),
+                ..
+            } => self
+                .get_target_area(target_id)
+                .or_else(|| self.get_panel_area(panel_id)),
+            _ => self.get_panel_area(panel_id),
+        }
+    }
+
+    pub fn record_panel_traits(&mut self, traits: PanelTraits) {
+        self.panel_traits.insert(traits.panel_id.clone(), traits);
+    }
+
+    pub fn get_panel_traits(&self, panel_id: &str) -> PanelTraits {
+        self.panel_traits
+            .get(panel_id)
+            .cloned()
+            .unwrap_or(PanelTraits {
+                panel_id: panel_id.to_string(),
+                ..PanelTraits::default()
+            })
+    }
+
+    pub fn record_target_rules(&mut self, target_id: String, rules: TargetRules) {
+        self.target_rules.insert(target_id, rules);
+    }
+
+    pub fn takes_tabs(&self, target_id: &str) -> bool {
+        self.target_rules
+            .get(target_id)
+            .is_some_and(|rules| rules.tabs)
+    }
+
+    /// `incoming` is how many panels the drop leaves in the target.
+    fn admit(&self, target_id: &str, panel_id: &str, incoming: usize) -> Result<(), DropRejection> {
+        let reject = |reason| {
+            Err(DropRejection {
+                panel_id: panel_id.to_string(),
+                target_id: target_id.to_string(),
+                reason,
+            })
+        };
+        if let (Some(target_area), Some(panel_area)) = (
+            self.get_target_area(target_id),
+            self.get_panel_area(panel_id),
+        ) {
+            let area_accepts = match self.area_sources.get(&target_area) {
+                Some(Some(sources)) => target_area == panel_area || sources.contains(&panel_area),
+                _ => true,
+            };
+            if !area_accepts {
+                return reject(RejectionReason::Area);
+            }
+        }
+        let rules = match self.target_rules.get(target_id) {
+            Some(rules) => rules,
+            None => return Ok(()),
+        };
+        if let Some(filter) = &rules.accepts {
+            if !filter.accepts(&self.get_panel_traits(panel_id)) {
+                return reject(RejectionReason::Filter);
+            }
+        }
+        match rules.capacity {
+            Some(capacity) if incoming > capacity => reject(RejectionReason::Capacity(capacity)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks a drop against the target's rules, and against the origin's rules
+    /// for every panel the drop would swap back into the dragged panel's origin.
+    pub fn check_drop(
+        &self,
+        panel_id: &str,
+        origin: &DragOrigin,
+        target_id: &str,
+        mode: DropMode,
+    ) -> Result<(), DropRejection> {
+        let origin_target = origin.get_snap_info().target_id;
+        if origin_target.as_deref() == Some(target_id) {
+            return Ok(());
+        }
+        if matches!(mode, DropMode::Split(_)) {
+            return self.admit(target_id, panel_id, 1);
+        }
+        if mode == DropMode::Stack {
+            let residents = self
+                .get_panels_in_target(target_id)
+                .into_iter()
+                .filter(|resident_id| resident_id != panel_id)
+                .count();
+            return self.admit(target_id, panel_id, residents + 1);
+        }
+        self.admit(target_id, panel_id, 1)?;
+        let origin_target = match origin_target {
+            Some(origin_target) => origin_target,
+            None => return Ok(()),
+        };
+        let displaced: Vec<String> = self
+            .get_panels_in_target(target_id)
+            .into_iter()
+            .filter(|displaced_id| displaced_id != panel_id)
+            .collect();
+        for displaced_id in &displaced {
+            self.admit(&origin_target, displaced_id, displaced.len())?;
+        }
+        Ok(())
+    }
+
+    pub fn accepts_drop(
+        &self,
+        panel_id: &str,
+        origin: &DragOrigin,
+        target_id: &str,
+        mode: DropMode,
+    ) -> bool {
+        self.check_drop(panel_id, origin, target_id, mode).is_ok()
+    }
+
+    pub fn is_tab_stack(&self, target_id: &str) -> bool {
+        self.tab_stacks.contains_key(target_id)
+    }
+
+    /// Adds a panel to a target's tabs, turning the panels already resting
+    /// there into a stack if it is not one yet.
+    pub fn add_tab(&mut self, target_id: &str, panel_id: String, index: Option<usize>) {
+        let residents: Vec<String> = self
+            .get_panels_in_target(target_id)
+            .into_iter()
+            .filter(|resident_id| *resident_id != panel_id)
+            .collect();
+        let stack = self
+            .tab_stacks
+            .entry(target_id.to_string())
+            .or_insert_with(|| TabStack {
+                active: residents.first().cloned().unwrap_or_default(),
+                panels: residents,
+            });
+        let index = index.unwrap_or(stack.panels.len());
+        stack.insert(panel_id.clone(), index);
+        self.leave_tab_stacks(&panel_id, Some(target_id));
+    }
+
+    pub fn select_tab(&mut self, panel_id: &str) -> bool {
+        match self
+            .tab_stacks
+            .values_mut()
+            .find(|stack| stack.contains(panel_id))
+        {
+            Some(stack) if stack.active != panel_id => {
+                stack.active = panel_id.to_string();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub fn get_tab_stack(&self, target_id: &str) -> Option<TabStack> {
+        self.tab_stacks.get(target_id).cloned()
+    }
+
+    /// Puts back a stack as it was, for undoing a drop that dissolved it.
+    pub fn restore_tab_stack(&mut self, target_id: String, stack: TabStack) {
+        for panel_id in &stack.panels {
+            self.leave_tab_stacks(panel_id, Some(&target_id));
+        }
+        self.tab_stacks.insert(target_id, stack);
+    }
+
+    pub fn get_tab_position(&self, panel_id: &str) -> Option<TabPosition> {
+        self.tab_stacks.values().find_map(|stack| {
+            let index = stack.panels.iter().position(|id| id == panel_id)?;
+            Some(TabPosition {
+                index,
+                count: stack.panels.len(),
+                active: stack.active == panel_id,
+            })
+        })
+    }
+
+    /// Removes a panel from every stack but the one in `keep`, dissolving
+    /// stacks left with a single tab.
+    fn leave_tab_stacks(&mut self, panel_id: &str, keep: Option<&str>) {
+        for (target_id, stack) in self.tab_stacks.iter_mut() {
+            if Some(target_id.as_str()) != keep {
+                stack.remove(panel_id);
+            }
+        }
+        self.tab_stacks.retain(|_, stack| stack.panels.len() > 1);
+    }
+
+    pub fn record_grid_sizes(&mut self, area_id: String, sizes: GridSizes) {
+        self.grid_sizes.insert(area_id, sizes);
+    }
+
+    pub fn get_grid_sizes(&self, area_id: &str) -> Option<&GridSizes> {
+        self.grid_sizes.get(area_id)
+    }
+
+    pub fn get_split(&self, target_id: &str) -> Option<TargetSplit> {
+        self.splits.get(target_id).cloned()
+    }
+
+    pub fn is_split_target(&self, target_id: &str) -> bool {
+        self.splits
+            .values()
+            .any(|split| split.target_id == target_id)
+    }
+
+    /// Divides a target along an edge. The new target appears once its
+    /// parent DragTarget renders it.
+    pub fn add_split(&mut self, split: TargetSplit) {
+        tracing::info!("splitting target: {:?}", split);
+        self.splits.insert(split.parent_id.clone(), split);
+    }
+
+    /// Splits where one side has been vacated, ignoring splits that are
+    /// split again or have placements queued into them.
+    pub fn get_vacated_splits(&self) -> Vec<TargetSplit> {
+        self.splits
+            .values()
+            .filter(|split| !self.splits.contains_key(&split.target_id))
+            .filter(|split| {
+                !self.pending.values().any(|placement| {
+                    matches!(placement, PanelPlacement::Snapped { target_id: Some(id), .. }
+                        if *id == split.target_id || *id == split.parent_id)
+                })
+            })
+            .filter(|split| {
+                self.get_panels_in_target(&split.target_id).is_empty()
+                    || self.get_panels_in_target(&split.parent_id).is_empty()
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Panels left in a split whose parent was vacated move back into the parent.
+    pub fn collapse_vacated_splits(&mut self) {
+        for split in self.get_vacated_splits() {
+            tracing::info!("collapsing split: {:?}", split);
+            for panel_id in self.get_panels_in_target(&split.target_id) {
+                let placement = PanelPlacement::Snapped {
+                    target_id: Some(split.parent_id.clone()),
+                    rect: self
+                        .get_target_rect(&split.parent_id)
+                        .unwrap_or_default()
+                        .into(),
+                };
+                self.queue_placement(panel_id, placement);
+            }
+            if let Some(stack) = self.tab_stacks.remove(&split.target_id) {
+                self.tab_stacks
+                    .entry(split.parent_id.clone())
+                    .or_insert(stack);
+            }
+            self.splits.remove(&split.parent_id);
+        }
+    }
+
+    pub fn get_active_breakpoint(&self, area_id: &str) -> Option<String> {
+        self.breakpoint_layouts.get(area_id)?.active.clone()
+    }
+
+    /// Keeps the area's arrangement under the breakpoint it leaves and restores
+    /// the new one's, re-flowing around its panel assignments on first entry.
+    pub fn switch_breakpoint(&mut self, area_id: &str, breakpoint: &Breakpoint) {
+        if self.get_active_breakpoint(area_id).as_ref() == Some(&breakpoint.name) {
+            return;
+        }
+        tracing::info!(
+            "area {} switching to breakpoint {}",
+            area_id,
+            breakpoint.name
+        );
+        let arrangement = self.get_area_arrangement(area_id);
+        let reflowed = self.get_reflowed_arrangement(area_id, &breakpoint.panels);
+        let layouts = self
+            .breakpoint_layouts
+            .entry(area_id.to_string())
+            .or_default();
+        if let Some(previous) = layouts.active.replace(breakpoint.name.clone()) {
+            layouts.panels.insert(previous, arrangement.clone());
+        }
+        let placements = match layouts.panels.get(&breakpoint.name) {
+            Some(saved) => saved.clone(),
+            None => reflowed,
+        };
+        for (panel_id, placement) in placements {
+            let current = arrangement.get(&panel_id);
+            let unchanged = match (&placement, current) {
+                (
+                    PanelPlacement::Snapped {
+                        target_id: Some(target_id),
+                        ..
+                    },
+                    Some(PanelPlacement::Snapped {
+                        target_id: Some(current_id),
+                        ..
+                    }),
+                ) => target_id == current_id,
+                (_, current) => current == Some(&placement),
+            };
+            // targets split off in another breakpoint may have merged away since
+            let missing_target = match &placement {
+                PanelPlacement::Snapped {
+                    target_id: Some(target_id),
+                    ..
+                } => !self.targets.contains_key(target_id),
+                _ => false,
+            };
+            if !unchanged && !missing_target {
+                self.queue_placement(panel_id, placement);
+            }
+        }
+    }
+
+    fn get_area_arrangement(&self, area_id: &str) -> BTreeMap<String, PanelPlacement> {
+        let mut arrangement = self.export_area(area_id).panels;
+        for (panel_id, placement) in &self.pending {
+            if self.get_placement_area(panel_id, placement).as_deref() == Some(area_id) {
+                arrangement.insert(panel_id.clone(), placement.clone());
+            }
+        }
+        arrangement
+    }
+
+    /// Panels pushed out by assigned panels fill the empty targets that take
+    /// them, in spatial order.
+    fn get_reflowed_arrangement(
+        &self,
+        area_id: &str,
+        assignments: &BTreeMap<String, String>,
+    ) -> BTreeMap<String, PanelPlacement> {
+        let mut panel_targets: BTreeMap<String, String> = self
+            .get_area_arrangement(area_id)
+            .into_iter()
+            .filter_map(|(panel_id, placement)| match placement {
+                PanelPlacement::Snapped {
+                    target_id: Some(target_id),
+                    ..
+                } => Some((panel_id, target_id)),
+                _ => None,
+            })
+            .collect();
+        let assignments: BTreeMap<&String, &String> = assignments
+            .iter()
+            .filter(|(panel_id, target_id)| {
+                self.get_panel_area(panel_id).as_deref() == Some(area_id)
+                    && self.targets.contains_key(*target_id)
+            })
+            .collect();
+        for (panel_id, target_id) in &assignments {
+            panel_targets.insert((*panel_id).clone(), (*target_id).clone());
+        }
+        let displaced: Vec<String> = panel_targets
+            .iter()
+            .filter(|(panel_id, target_id)| {
+                !assignments.contains_key(panel_id)
+                    && assignments.values().any(|assigned| *assigned == *target_id)
+                    && !self.is_tab_stack(target_id)
+            })
+            .map(|(panel_id, _)| panel_id.clone())
+            .collect();
+        let mut empty_targets: Vec<String> = self
+            .get_targets_in_spatial_order()
+            .into_iter()
+            .filter(|target_id| {
+                self.get_target_area(target_id).as_deref() == Some(area_id)
+                    && !panel_targets.iter().any(|(panel_id, resident)| {
+                        resident == target_id && !displaced.contains(panel_id)
+                    })
+            })
+            .collect();
+        for panel_id in displaced {
+            match empty_targets
+                .iter()
+                .position(|target_id| self.admit(target_id, &panel_id, 1).is_ok())
+            {
+                Some(index) => {
+                    panel_targets.insert(panel_id, empty_targets.remove(index));
+                }
+                None => tracing::info!("no empty target left for {}", panel_id),
+            }
+        }
+        panel_targets
+            .into_iter()
+            .map(|(panel_id, target_id)| {
+                let rect = self.get_target_rect(&target_id).unwrap_or_default();
+                let placement = PanelPlacement::Snapped {
+                    target_id: Some(target_id),
+                    rect: rect.into(),
+                };
+                (panel_id, placement)
+            })
+            .collect()
+    }
+
+    pub fn involves_area(&self, area_id: &str, event: &PanelDragEvent) -> bool {
+        let destination_area = event
+            .destination
+            .as_ref()
+            .and_then(|destination| destination.target_id.as_ref())
+            .and_then(|target_id| self.get_target_area(target_id));
+        self.get_panel_area(&event.panel_id).as_deref() == Some(area_id)
+            || destination_area.as_deref() == Some(area_id)
+    }
+
+    pub fn get_panel(&self, panel_id: &str) -> Option<&PanelPlacement> {
+        self.panels.get(panel_id)
+    }
+
+    pub fn get_origin(&self, panel_id: &str) -> Option<DragOrigin> {
+        match self.panels.get(panel_id)? {
+            PanelPlacement::Snapped { target_id, rect } => {
+                let rect = target_id
+                    .as_ref()
+                    .and_then(|target_id| self.get_target_rect(target_id))
+                    .unwrap_or((*rect).into());
+                Some(DragOrigin::Snapped(SnapInfo::new(target_id.clone(), rect)))
+            }
+            PanelPlacement::Released { rect } => Some(DragOrigin::Free((*rect).into())),
+        }
+    }
+
+    pub fn get_home_target(&self, panel_id: &str) -> Option<String> {
+        self.home_targets.get(panel_id).cloned()
+    }
+
+    pub fn record_home_target(&mut self, panel_id: String, target_id: String) {
+        self.home_targets.insert(panel_id, target_id);
+    }
+
+    pub fn record_panel_title(&mut self, panel_id: String, title: String) {
+        self.panel_titles.insert(panel_id, title);
+    }
+
+    pub fn get_panel_title(&self, panel_id: &str) -> String {
+        self.panel_titles
+            .get(panel_id)
+            .cloned()
+            .unwrap_or(panel_id.to_string())
+    }
+
+    pub fn record_target_label(&mut self, target_id: String, label: Option<String>) {
+        match label {
+            Some(label) => self.target_labels.insert(target_id, label),
+            None => self.target_labels.remove(&target_id),
+        };
+    }
+
+    /// The label a target was given, falling back to its position in spatial order.
+    pub fn get_target_label(&self, target_id: &str) -> String {
+        if let Some(label) = self.target_labels.get(target_id) {
+            return label.clone();
+        }
+        match self
+            .get_targets_in_spatial_order()
+            .iter()
+            .position(|id| id == target_id)
+        {
+            Some(index) => format!("target {}", index + 1),
+            None => "target".to_string(),
+        }
+    }
+
+    pub fn get_targets_in_spatial_order(&self) -> Vec<String> {
+        let mut targets: Vec<(&String, &Rect<f64, AreaSpace>)> = self.targets.iter().collect();
+        targets.sort_by(|(_, a), (_, b)| {
+            a.origin
+                .y
+                .total_cmp(&b.origin.y)
+                .then(a.origin.x.total_cmp(&b.origin.x))
+        });
+        targets
+            .into_iter()
+            .map(|(target_id, _)| target_id.clone())
+            .collect()
+    }
+
+    pub fn request_focus(&mut self, panel_id: Option<String>) {
+        self.focus_request = panel_id;
+    }
+
+    pub fn get_focus_request(&self) -> Option<String> {
+        self.focus_request.clone()
+    }
+
+    pub fn get_panels_in_target(&self, target_id: &str) -> Vec<String> {
+        self.panels
+            .iter()
+            .filter(|(_, placement)| {
+                matches!(placement, PanelPlacement::Snapped { target_id: Some(id), .. } if id == target_id)
+            })
+            .map(|(panel_id, _)| panel_id.clone())
+            .collect()
+    }
+
+    /// Panels still on their way to a queued placement leave the tab stacks untouched.
+    pub fn record_panel(&mut self, panel_id: String, placement: PanelPlacement) {
+        self.unsettled.remove(&panel_id);
+        if !self.pending.contains_key(&panel_id) {
+            let target_id = match &placement {
+                PanelPlacement::Snapped {
+                    target_id: Some(target_id),
+                    ..
+                } => Some(target_id.clone()),
+                _ => None,
+            };
+            self.leave_tab_stacks(&panel_id, target_id.as_deref());
+            let stack = match &target_id {
+                Some(target_id) => self.tab_stacks.get_mut(target_id),
+                None => None,
+            };
+            if let Some(stack) = stack {
+                if !stack.contains(&panel_id) {
+                    stack.panels.push(panel_id.clone());
+                }
+            }
+        }
+        self.panels.insert(panel_id, placement);
+    }
+
+    /// Marks a panel as grabbed or animating so autosave waits for it.
+    pub fn mark_unsettled(&mut self, panel_id: String) {
+        self.unsettled.insert(panel_id);
+    }
+
+    pub fn is_unsettled(&self, panel_id: &str) -> bool {
+        self.unsettled.contains(panel_id)
+    }
+
+    pub fn is_settled(&self) -> bool {
+        self.unsettled.is_empty()
+    }
+
+    pub fn remove_panel(&mut self, panel_id: &str) {
+        self.panels.remove(panel_id);
+        self.pending.remove(panel_id);
+        self.unsettled.remove(panel_id);
+        self.home_targets.remove(panel_id);
+        self.panel_titles.remove(panel_id);
+        self.panel_areas.remove(panel_id);
+        self.panel_traits.remove(panel_id);
+        self.leave_tab_stacks(panel_id, None);
+    }
+
+    pub fn get_target_rect(&self, target_id: &str) -> Option<Rect<f64, AreaSpace>> {
+        self.targets.get(target_id).copied()
+    }
+
+    pub fn register_target(&mut self, target_id: String, rect: Rect<f64, AreaSpace>) {
+        self.targets.insert(target_id, rect);
+    }
+
+    pub fn remove_target(&mut self, target_id: &str) {
+        self.targets.remove(target_id);
+        self.target_labels.remove(target_id);
+        self.target_areas.remove(target_id);
+        self.target_rules.remove(target_id);
+        self.tab_stacks.remove(target_id);
+        self.splits.remove(target_id);
+    }
+
+    pub fn queue_placement(&mut self, panel_id: String, placement: PanelPlacement) {
+        self.pending.insert(panel_id, placement);
+    }
+
+    pub fn has_pending(&self, panel_id: &str) -> bool {
+        self.pending.contains_key(panel_id)
+    }
+
+    /// True while a mounted panel is still on its way to a queued placement.
+    pub fn has_pending_placements(&self) -> bool {
+        self.pending
+            .keys()
+            .any(|panel_id| self.panel_areas.contains_key(panel_id))
+    }
+
+    pub fn clear_pending(&mut self, panel_id: &str) {
+        self.pending.remove(panel_id);
+    }
+
+    /// Turns a queued placement into a rest state, using the current rect of
+    /// the placement's target. Returns None while the target is unmeasured.
+    pub fn resolve_pending(&self, panel_id: &str) -> Option<DraggableRestStates> {
+        match self.pending.get(panel_id)? {
+            PanelPlacement::Snapped {
+                target_id: Some(target_id),
+                ..
+            } => {
+                let rect = self.get_target_rect(target_id)?;
+                Some(DraggableRestStates::Snapped(DraggableSnapStates::Final(
+                    SnapInfo::new(Some(target_id.clone()), rect),
+                )))
+            }
+            PanelPlacement::Snapped {
+                target_id: None,
+                rect,
+            } => Some(DraggableRestStates::Snapped(DraggableSnapStates::Final(
+                SnapInfo::new(None, (*rect).into()),
+            ))),
+            PanelPlacement::Released { rect } => {
+                Some(DraggableRestStates::Released((*rect).into()))
+            }
+        }
+    }
+}
+
+/// The ids taken by mounted areas, targets and panels. It is kept out of
+/// LayoutState so components can claim their ids while rendering.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IdRegistry {
+    panel_ids: HashSet<String>,
+    target_ids: HashSet<String>,
+    area_ids: HashSet<String>,
+}
+
+impl IdRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A duplicate id is replaced with a generated one, and returned with the
+    /// error to report.
+    pub fn claim_panel_id(&mut self, requested: Option<String>) -> (String, Option<DragError>) {
+        Self::claim_id(&mut self.panel_ids, requested)
+    }
+
+    /// `:` is reserved for the targets split off another, whose ids are
+    /// `"{parent}:{edge}"`.
+    pub fn claim_target_id(
+        &mut self,
+        requested: Option<String>,
+        split_target: bool,
+    ) -> (String, Option<DragError>) {
+        match requested {
+            Some(id) if id.contains(':') && !split_target => {
+                tracing::error!("reserved id: {}", id);
+                let (generated, _) = Self::claim_id(&mut self.target_ids, None);
+                (generated, Some(DragError(DragErrorType::ReservedId(id))))
+            }
+            requested => Self::claim_id(&mut self.target_ids, requested),
+        }
+    }
+
+    pub fn claim_area_id(&mut self, requested: Option<String>) -> (String, Option<DragError>) {
+        Self::claim_id(&mut self.area_ids, requested)
+    }
+
+    fn claim_id(
+        claimed: &mut HashSet<String>,
+        requested: Option<String>,
+    ) -> (String, Option<DragError>) {
+        let mut error = None;
+        if let Some(id) = requested {
+            if claimed.insert(id.clone()) {
+                return (id, None);
+            }
+            tracing::error!("duplicate id: {}", id);
+            error = Some(DragError(DragErrorType::DuplicateId(id)));
+        }
+        let id = uuid::Uuid::new_v4().to_string();
+        claimed.insert(id.clone());
+        (id, error)
+    }
+
+    pub fn release_panel_id(&mut self, panel_id: &str) {
+        self.panel_ids.remove(panel_id);
+    }
+
+    pub fn release_target_id(&mut self, target_id: &str) {
+        self.target_ids.remove(target_id);
+    }
+
+    pub fn release_area_id(&mut self, area_id: &str) {
+        self.area_ids.remove(area_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::draggable::{DropFilter, Edge};
+    use dioxus_elements::geometry::euclid::Size2D;
+
+    fn snapped(target_id: &str) -> PanelPlacement {
+        PanelPlacement::Snapped {
+            target_id: Some(target_id.to_string()),
+            rect: Rect::new(Point2D::new(0., 0.), Size2D::new(100., 100.)).into(),
+        }
+    }
+
+    fn origin(target_id: &str) -> DragOrigin {
+        DragOrigin::Snapped(SnapInfo::new(
+            Some(target_id.to_string()),
+            Rect::new(Point2D::new(0., 0.), Size2D::new(100., 100.)),
+        ))
+    }
+
+    fn with_capacity(layout: &mut LayoutState, target_id: &str, capacity: usize) {
+        layout.record_target_rules(
+            target_id.to_string(),
+            TargetRules {
+                capacity: Some(capacity),
+                ..TargetRules::default()
+            },
+        );
+    }
+
+    fn rejection(panel_id: &str, target_id: &str, reason: RejectionReason) -> DropRejection {
+        DropRejection {
+            panel_id: panel_id.to_string(),
+            target_id: target_id.to_string(),
+            reason,
+        }
+    }
+
+    #[test]
+    fn swaps_count_only_the_dragged_panel_against_capacity() {
+        let mut layout = LayoutState::new();
+        with_capacity(&mut layout, "one", 1);
+        layout.record_panel("b".into(), snapped("one"));
+        assert!(layout
+            .check_drop("a", &origin("two"), "one", DropMode::Swap)
+            .is_ok());
+    }
+
+    #[test]
+    fn stacks_count_every_resident_against_capacity() {
+        let mut layout = LayoutState::new();
+        with_capacity(&mut layout, "one", 2);
+        layout.record_panel("b".into(), snapped("one"));
+        assert!(layout
+            .check_drop("a", &origin("two"), "one", DropMode::Stack)
+            .is_ok());
+        layout.record_panel("c".into(), snapped("one"));
+        assert_eq!(
+            layout.check_drop("a", &origin("two"), "one", DropMode::Stack),
+            Err(rejection("a", "one", RejectionReason::Capacity(2)))
+        );
+    }
+
+    #[test]
+    fn splits_take_one_panel() {
+        let mut layout = LayoutState::new();
+        with_capacity(&mut layout, "one", 1);
+        layout.record_panel("b".into(), snapped("one"));
+        layout.record_panel("c".into(), snapped("one"));
+        assert!(layout
+            .check_drop("a", &origin("two"), "one", DropMode::Split(Edge::Left))
+            .is_ok());
+        with_capacity(&mut layout, "one", 0);
+        assert_eq!(
+            layout.check_drop("a", &origin("two"), "one", DropMode::Split(Edge::Left)),
+            Err(rejection("a", "one", RejectionReason::Capacity(0)))
+        );
+    }
+
+    #[test]
+    fn swapped_panels_must_fit_the_origin() {
+        let mut layout = LayoutState::new();
+        with_capacity(&mut layout, "two", 1);
+        layout.record_panel("b".into(), snapped("one"));
+        layout.record_panel("c".into(), snapped("one"));
+        assert_eq!(
+            layout.check_drop("a", &origin("two"), "one", DropMode::Swap),
+            Err(rejection("b", "two", RejectionReason::Capacity(1)))
+        );
+        // a free panel leaves nowhere for the residents to go
+        let free = DragOrigin::Free(Rect::new(Point2D::new(0., 0.), Size2D::new(1., 1.)));
+        assert!(layout.check_drop("a", &free, "one", DropMode::Swap).is_ok());
+    }
+
+    #[test]
+    fn filters_and_areas_reject_before_capacity() {
+        let mut layout = LayoutState::new();
+        layout.record_target_rules(
+            "one".into(),
+            TargetRules {
+                accepts: Some(DropFilter::groups(vec!["tools".into()])),
+                capacity: Some(0),
+                tabs: false,
+            },
+        );
+        assert_eq!(
+            layout.check_drop("a", &origin("two"), "one", DropMode::Swap),
+            Err(rejection("a", "one", RejectionReason::Filter))
+        );
+        layout.register_area("left".into(), Some(Vec::new()));
+        layout.record_target_area("one".into(), "left".into());
+        layout.record_panel_area("a".into(), "right".into());
+        assert_eq!(
+            layout.check_drop("a", &origin("two"), "one", DropMode::Swap),
+            Err(rejection("a", "one", RejectionReason::Area))
+        );
+    }
+
+    #[test]
+    fn drops_back_into_the_origin_are_always_accepted() {
+        let mut layout = LayoutState::new();
+        with_capacity(&mut layout, "one", 0);
+        assert!(layout
+            .check_drop("a", &origin("one"), "one", DropMode::Stack)
+            .is_ok());
+    }
+
+    #[test]
+    fn vacated_splits_collapse_into_their_parent() {
+        let mut layout = LayoutState::new();
+        let split = TargetSplit::new("one".into(), Edge::Right);
+        layout.add_split(split.clone());
+        layout.record_panel("a".into(), snapped("one"));
+        layout.record_panel("b".into(), snapped(&split.target_id));
+        assert!(layout.is_split_target("one:right"));
+        assert!(layout.get_vacated_splits().is_empty());
+
+        layout.record_panel("a".into(), snapped("two"));
+        assert_eq!(layout.get_vacated_splits(), vec![split]);
+        layout.collapse_vacated_splits();
+        assert_eq!(layout.get_split("one"), None);
+        assert!(layout.has_pending("b"));
+        assert!(layout.get_vacated_splits().is_empty());
+    }
+}
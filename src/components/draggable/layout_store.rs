@@ -0,0 +1,257 @@
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use super::{DragError, LayoutSnapshot};
+
+const DEFAULT_AUTOSAVE_DEBOUNCE_MS: u64 = 500;
+
+pub trait LayoutStore {
+    fn load(&self, name: &str) -> Result<Option<LayoutSnapshot>, DragError>;
+    fn save(&self, name: &str, snapshot: &LayoutSnapshot) -> Result<(), DragError>;
+    fn list(&self) -> Result<Vec<String>, DragError>;
+    fn delete(&self, name: &str) -> Result<(), DragError>;
+}
+
+#[cfg(feature = "serde")]
+fn store_error(message: impl ToString) -> DragError {
+    DragError(super::DragErrorType::LayoutStore(message.to_string()))
+}
+
+/// Keeps layouts for the lifetime of the store.
+#[derive(Default)]
+pub struct MemoryLayoutStore {
+    layouts: RefCell<BTreeMap<String, LayoutSnapshot>>,
+}
+
+impl MemoryLayoutStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl LayoutStore for MemoryLayoutStore {
+    fn load(&self, name: &str) -> Result<Option<LayoutSnapshot>, DragError> {
+        Ok(self.layouts.borrow().get(name).cloned())
+    }
+
+    fn save(&self, name: &str, snapshot: &LayoutSnapshot) -> Result<(), DragError> {
+        self.layouts
+            .borrow_mut()
+            .insert(name.to_string(), snapshot.clone());
+        Ok(())
+    }
+
+    fn list(&self) -> Result<Vec<String>, DragError> {
+        Ok(self.layouts.borrow().keys().cloned().collect())
+    }
+
+    fn delete(&self, name: &str) -> Result<(), DragError> {
+        self.layouts.borrow_mut().remove(name);
+        Ok(())
+    }
+}
+
+/// Stores layouts as JSON in the browser's localStorage under a key prefix.
+#[cfg(all(feature = "serde", target_arch = "wasm32"))]
+pub struct LocalStorageLayoutStore {
+    prefix: String,
+}
+
+#[cfg(all(feature = "serde", target_arch = "wasm32"))]
+impl LocalStorageLayoutStore {
+    pub fn new(prefix: impl ToString) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    fn storage(&self) -> Result<web_sys::Storage, DragError> {
+        web_sys::window()
+            .ok_or_else(|| store_error("no window"))?
+            .local_storage()
+            .map_err(|err| store_error(format!("{:?}", err)))?
+            .ok_or_else(|| store_error("localStorage is unavailable"))
+    }
+
+    fn key(&self, name: &str) -> String {
+        format!("{}{}", self.prefix, name)
+    }
+}
+
+#[cfg(all(feature = "serde", target_arch = "wasm32"))]
+impl Default for LocalStorageLayoutStore {
+    fn default() -> Self {
+        Self::new("dx-magnet-layout:")
+    }
+}
+
+#[cfg(all(feature = "serde", target_arch = "wasm32"))]
+impl LayoutStore for LocalStorageLayoutStore {
+    fn load(&self, name: &str) -> Result<Option<LayoutSnapshot>, DragError> {
+        let json = self
+            .storage()?
+            .get_item(&self.key(name))
+            .map_err(|err| store_error(format!("{:?}", err)))?;
+        match json {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(store_error),
+            None => Ok(None),
+        }
+    }
+
+    fn save(&self, name: &str, snapshot: &LayoutSnapshot) -> Result<(), DragError> {
+        let json = serde_json::to_string(snapshot).map_err(store_error)?;
+        self.storage()?
+            .set_item(&self.key(name), &json)
+            .map_err(|err| store_error(format!("{:?}", err)))
+    }
+
+    fn list(&self) -> Result<Vec<String>, DragError> {
+        let storage = self.storage()?;
+        let length = storage
+            .length()
+            .map_err(|err| store_error(format!("{:?}", err)))?;
+        let mut names = Vec::new();
+        for index in 0..length {
+            if let Ok(Some(key)) = storage.key(index) {
+                if let Some(name) = key.strip_prefix(&self.prefix) {
+                    names.push(name.to_string());
+                }
+            }
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&self, name: &str) -> Result<(), DragError> {
+        self.storage()?
+            .remove_item(&self.key(name))
+            .map_err(|err| store_error(format!("{:?}", err)))
+    }
+}
+
+#[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+pub struct FileLayoutStore {
+    directory: std::path::PathBuf,
+}
+
+#[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+impl FileLayoutStore {
+    pub fn new(directory: impl Into<std::path::PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, name: &str) -> Result<std::path::PathBuf, DragError> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(store_error(format!("invalid layout name: {}", name)));
+        }
+        Ok(self.directory.join(format!("{}.json", name)))
+    }
+}
+
+#[cfg(all(feature = "serde", not(target_arch = "wasm32")))]
+impl LayoutStore for FileLayoutStore {
+    fn load(&self, name: &str) -> Result<Option<LayoutSnapshot>, DragError> {
+        match std::fs::read_to_string(self.path(name)?) {
+            Ok(json) => serde_json::from_str(&json).map(Some).map_err(store_error),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(store_error(err)),
+        }
+    }
+
+    fn save(&self, name: &str, snapshot: &LayoutSnapshot) -> Result<(), DragError> {
+        let path = self.path(name)?;
+        std::fs::create_dir_all(&self.directory).map_err(store_error)?;
+        let json = serde_json::to_string_pretty(snapshot).map_err(store_error)?;
+        std::fs::write(path, json).map_err(store_error)
+    }
+
+    fn list(&self) -> Result<Vec<String>, DragError> {
+        let entries = match std::fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(store_error(err)),
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+
+    fn delete(&self, name: &str) -> Result<(), DragError> {
+        match std::fs::remove_file(self.path(name)?) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(store_error(err)),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Restores the named layout on mount and saves it once a drop, undo or
+/// resize has settled for the debounce duration.
+#[derive(Clone)]
+pub struct LayoutPersistence {
+    store: Rc<dyn LayoutStore>,
+    name: String,
+    debounce: web_time::Duration,
+}
+
+impl LayoutPersistence {
+    pub fn new(store: impl LayoutStore + 'static, name: impl ToString) -> Self {
+        Self::from_rc(Rc::new(store), name)
+    }
+
+    pub fn from_rc(store: Rc<dyn LayoutStore>, name: impl ToString) -> Self {
+        Self {
+            store,
+            name: name.to_string(),
+            debounce: web_time::Duration::from_millis(DEFAULT_AUTOSAVE_DEBOUNCE_MS),
+        }
+    }
+
+    pub fn with_debounce(mut self, debounce: web_time::Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_debounce(&self) -> web_time::Duration {
+        self.debounce
+    }
+
+    pub fn get_store(&self) -> Rc<dyn LayoutStore> {
+        self.store.clone()
+    }
+
+    pub fn load(&self) -> Result<Option<LayoutSnapshot>, DragError> {
+        self.store.load(&self.name)
+    }
+
+    pub fn save(&self, snapshot: &LayoutSnapshot) -> Result<(), DragError> {
+        tracing::info!("saving layout {}", self.name);
+        self.store.save(&self.name, snapshot)
+    }
+}
+
+impl PartialEq for LayoutPersistence {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.store, &other.store)
+            && self.name == other.name
+            && self.debounce == other.debounce
+    }
+}
+
+pub(crate) async fn sleep(duration: web_time::Duration) {
+    #[cfg(target_arch = "wasm32")]
+    gloo_timers::future::sleep(duration).await;
+    #[cfg(not(target_arch = "wasm32"))]
+    tokio::time::sleep(duration).await;
+}
@@ -7,17 +7,62 @@ pub use draggable_state::*;
 mod targets;
 pub use targets::*;
 
+mod layout_snapshot;
+pub use layout_snapshot::*;
+
+mod layout_state;
+pub use layout_state::*;
+
+mod drag_coordinator;
+pub use drag_coordinator::*;
+
+mod drag_area_controller;
+pub use drag_area_controller::*;
+
+mod layout_history;
+pub use layout_history::*;
+
+mod layout_store;
+pub use layout_store::*;
+
 mod drag_error;
 pub use drag_error::*;
 
+mod drop_rules;
+pub use drop_rules::*;
+
 mod drag_transition;
 use drag_transition::*;
 
 mod dragarea_state;
-use dragarea_state::*;
+pub use dragarea_state::*;
 
 mod state_utils;
-use state_utils::*;
+pub use state_utils::*;
+
+mod drag_events;
+pub use drag_events::*;
+
+mod announcements;
+use announcements::*;
+
+mod pointer_press;
+use pointer_press::*;
 
 mod draggable_render_data;
 use draggable_render_data::*;
+
+mod breakpoints;
+pub use breakpoints::*;
+
+mod grid_placement;
+pub use grid_placement::*;
+
+mod splitters;
+pub(crate) use splitters::*;
+
+mod resize_observer;
+use resize_observer::*;
+
+mod handle_keys;
+use handle_keys::*;
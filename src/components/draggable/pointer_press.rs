@@ -0,0 +1,147 @@
+use super::AreaSpace;
+use dioxus::prelude::*;
+use dioxus_elements::geometry::{euclid::Point2D, ElementSpace};
+
+/// How far a touch may wander before a long press is abandoned, even if the
+/// browser has not taken it over as a scroll yet.
+const LONG_PRESS_TOLERANCE: f64 = 10.;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum PressProgress {
+    Waiting,
+    Start,
+    Abandon,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PendingPress {
+    pub press_id: u64,
+    pub pointer_id: i32,
+    pub grab_point: Point2D<f64, ElementSpace>,
+    pub start_pos: Point2D<f64, AreaSpace>,
+    pub current_pos: Point2D<f64, AreaSpace>,
+    pub long_press: bool,
+}
+
+impl PendingPress {
+    pub fn new(
+        press_id: u64,
+        pointer_id: i32,
+        grab_point: Point2D<f64, ElementSpace>,
+        pos: Point2D<f64, AreaSpace>,
+        long_press: bool,
+    ) -> Self {
+        Self {
+            press_id,
+            pointer_id,
+            grab_point,
+            start_pos: pos,
+            current_pos: pos,
+            long_press,
+        }
+    }
+
+    /// A long press is abandoned once it wanders.
+    pub fn update_pos(
+        &mut self,
+        pos: Point2D<f64, AreaSpace>,
+        drag_distance: f64,
+    ) -> PressProgress {
+        self.current_pos = pos;
+        let distance = (pos - self.start_pos).length();
+        match self.long_press {
+            true if distance > LONG_PRESS_TOLERANCE => PressProgress::Abandon,
+            false if distance >= drag_distance => PressProgress::Start,
+            _ => PressProgress::Waiting,
+        }
+    }
+}
+
+/// Touch pointers wait for a long press so they can still scroll the page.
+/// A scroll that starts first cancels the pointer, which abandons the press.
+pub(crate) fn needs_long_press(pointer_type: &str) -> bool {
+    pointer_type == "touch"
+}
+
+/// Keeps a drag receiving moves and its release outside the area or over an iframe.
+pub(crate) fn capture_pointer(handle: &MountedData, pointer_id: i32) {
+    #[cfg(target_arch = "wasm32")]
+    if let Some(element) = handle.downcast::<web_sys::Element>() {
+        if let Err(err) = element.set_pointer_capture(pointer_id) {
+            tracing::error!("could not capture pointer {}: {:?}", pointer_id, err);
+        }
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = handle;
+        tracing::info!(
+            "pointer capture is only supported on web, not capturing {}",
+            pointer_id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn start_press(long_press: bool) -> PendingPress {
+        PendingPress::new(1, 1, Point2D::zero(), Point2D::new(100., 100.), long_press)
+    }
+
+    #[test]
+    fn presses_wait_below_the_drag_distance() {
+        let mut press = start_press(false);
+        assert_eq!(
+            press.update_pos(Point2D::new(103., 104.), 6.),
+            PressProgress::Waiting
+        );
+        assert_eq!(press.current_pos, Point2D::new(103., 104.));
+    }
+
+    #[test]
+    fn presses_start_once_they_reach_the_drag_distance() {
+        let mut press = start_press(false);
+        assert_eq!(
+            press.update_pos(Point2D::new(103., 104.), 5.),
+            PressProgress::Start
+        );
+        // the distance is measured from where the press began, not the last move
+        let mut press = start_press(false);
+        press.update_pos(Point2D::new(102., 100.), 5.);
+        assert_eq!(
+            press.update_pos(Point2D::new(104., 100.), 5.),
+            PressProgress::Waiting
+        );
+        assert_eq!(
+            press.update_pos(Point2D::new(106., 100.), 5.),
+            PressProgress::Start
+        );
+    }
+
+    #[test]
+    fn long_presses_never_start_from_moving() {
+        let mut press = start_press(true);
+        assert_eq!(
+            press.update_pos(Point2D::new(108., 100.), 5.),
+            PressProgress::Waiting
+        );
+        assert_eq!(
+            press.update_pos(Point2D::new(100., 100.), 5.),
+            PressProgress::Waiting
+        );
+    }
+
+    #[test]
+    fn long_presses_are_abandoned_once_they_wander() {
+        let mut press = start_press(true);
+        assert_eq!(
+            press.update_pos(Point2D::new(100., 100. + LONG_PRESS_TOLERANCE), 5.),
+            PressProgress::Waiting
+        );
+        assert_eq!(
+            press.update_pos(Point2D::new(100., 111.), 5.),
+            PressProgress::Abandon
+        );
+    }
+}
@@ -0,0 +1,67 @@
+use std::rc::Rc;
+
+use dioxus::prelude::*;
+
+/// Counts how often an element has changed size. Observed on web only.
+pub(crate) fn use_resize_count(element: Signal<Option<Rc<MountedData>>>) -> Signal<u64> {
+    let mut count = use_signal(|| 0u64);
+    let mut watch = use_signal(|| None as Option<ResizeWatch>);
+    use_effect(move || {
+        if let Some(element) = element.read().as_ref() {
+            watch.set(ResizeWatch::new(element, move || *count.write() += 1));
+        }
+    });
+    use_drop(move || {
+        if let Ok(mut watch) = watch.try_write() {
+            watch.take();
+        }
+    });
+    count
+}
+
+#[cfg(target_arch = "wasm32")]
+struct ResizeWatch {
+    observer: web_sys::ResizeObserver,
+    _callback: wasm_bindgen::closure::Closure<dyn FnMut(wasm_bindgen::JsValue)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl ResizeWatch {
+    fn new(element: &MountedData, mut on_resize: impl FnMut() + 'static) -> Option<Self> {
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+        let element = element.downcast::<web_sys::Element>()?;
+        let callback = Closure::<dyn FnMut(JsValue)>::new(move |_entries: JsValue| on_resize());
+        let observer = match web_sys::ResizeObserver::new(callback.as_ref().unchecked_ref()) {
+            Ok(observer) => observer,
+            Err(err) => {
+                tracing::error!("could not create resize observer: {:?}", err);
+                return None;
+            }
+        };
+        observer.observe(element);
+        Some(Self {
+            observer,
+            _callback: callback,
+        })
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for ResizeWatch {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct ResizeWatch;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ResizeWatch {
+    fn new(element: &MountedData, on_resize: impl FnMut() + 'static) -> Option<Self> {
+        let _ = (element, on_resize);
+        tracing::info!("resize observation is only supported on web");
+        None
+    }
+}
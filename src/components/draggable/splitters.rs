@@ -0,0 +1,248 @@
+use std::rc::Rc;
+
+use crate::components::draggable::*;
+use dioxus::prelude::*;
+use dioxus_elements::geometry::euclid::Rect;
+
+const SPLITTER_THICKNESS: f64 = 8.;
+
+/// How far an arrow key moves a focused splitter, in area pixels.
+const SPLITTER_KEY_STEP: f64 = 10.;
+
+const SPLITTER_STYLES: &str = "
+    position: absolute;
+    z-index: 200;
+    touch-action: none;
+";
+
+const COLUMN_SPLITTER_STYLES: &str = "
+    cursor: col-resize;
+";
+
+const ROW_SPLITTER_STYLES: &str = "
+    cursor: row-resize;
+";
+
+const SPLITTER_KEYS: &[&str] = &["ArrowUp", "ArrowDown", "ArrowLeft", "ArrowRight"];
+
+const ACTIVE_SPLITTER_STYLES: &str = "
+    background-color: var(--accent_0);
+";
+
+#[component]
+pub(crate) fn CellSplitters(
+    cell_id: String,
+    cell: Signal<Option<Rc<MountedData>>>,
+    column_line: u64,
+    rows: Option<u64>,
+    gap: f64,
+    padding: f64,
+) -> Element {
+    let mut grid = use_context::<Signal<GridData>>();
+    let mut cell_rect = use_signal(|| None as Option<Rect<f64, AreaSpace>>);
+
+    // resizing any track or the window can move the cell, so it is measured again
+    let sizes = use_memo(move || grid.read().get_sizes().clone());
+    let area_rect = use_memo(move || grid.read().get_area_rect());
+    let area_element = use_context::<AreaElement>();
+    let cell_resizes = use_resize_count(cell);
+    use_effect(move || {
+        let _area = area_rect.read();
+        let _trig = cell.read();
+        let _sizes = sizes.read();
+        let _resizes = cell_resizes.read();
+        spawn(async move {
+            let element = cell.peek().clone();
+            if let Some(element) = element {
+                if let Some(rect) = measure_in_area(element, area_element, grid).await {
+                    cell_rect.set(Some(rect));
+                }
+            }
+        });
+    });
+
+    let column_cell_id = cell_id.clone();
+    let splitters = use_memo(move || {
+        let rect = match cell_rect() {
+            Some(rect) => rect,
+            None => return Vec::new(),
+        };
+        let grid = grid.read();
+        let mut splitters = Vec::new();
+        let column_gap = grid.get_gap();
+        // a cell ending on the first or last line has no neighbour to trade width with
+        if (2..=grid.get_columns() as u64).contains(&column_line) {
+            let boundary = (column_line - 1) as usize;
+            let line = grid.get_column_lines().get(boundary - 1).copied();
+            splitters.push((
+                format!("column-{}", column_cell_id),
+                SplitterTrack::Column(boundary),
+                line.unwrap_or_default(),
+                Rect::new(
+                    (
+                        rect.max_x() + column_gap / 2. - SPLITTER_THICKNESS / 2.,
+                        rect.origin.y,
+                    )
+                        .into(),
+                    (SPLITTER_THICKNESS, rect.height()).into(),
+                ),
+            ));
+        }
+        if let Some(rows) = rows {
+            let height = get_rows_height(rect.height(), rows, gap, padding);
+            for (index, line) in grid.get_row_lines(&column_cell_id, rows).iter().enumerate() {
+                let y = rect.origin.y + padding + height * line + gap * (index as f64 + 0.5);
+                splitters.push((
+                    format!("row-{}-{}", column_cell_id, index + 1),
+                    SplitterTrack::Row(index + 1),
+                    *line,
+                    Rect::new(
+                        (rect.origin.x + padding, y - SPLITTER_THICKNESS / 2.).into(),
+                        (rect.width() - padding * 2., SPLITTER_THICKNESS).into(),
+                    ),
+                ));
+            }
+        }
+        splitters
+    });
+
+    rsx! {
+        for (key, track, line, rect) in splitters() {
+            Splitter {
+                key: "{key}",
+                track: track,
+                line: line,
+                rect: rect,
+                on_move: {
+                    let cell_id = cell_id.clone();
+                    move |delta: f64| match track {
+                        SplitterTrack::Column(boundary) => {
+                            grid.write().move_column_line(boundary, delta)
+                        }
+                        SplitterTrack::Row(boundary) => {
+                            let height = cell_rect.peek().map_or(0., |rect| {
+                                get_rows_height(rect.height(), rows.unwrap_or_default(), gap, padding)
+                            });
+                            grid.write().move_row_line(
+                                &cell_id,
+                                rows.unwrap_or_default(),
+                                boundary,
+                                delta,
+                                height,
+                            )
+                        }
+                    }
+                },
+            }
+        }
+    }
+}
+
+fn get_rows_height(cell_height: f64, rows: u64, gap: f64, padding: f64) -> f64 {
+    cell_height - gap * (rows as f64 - 1.).max(0.) - padding * 2.
+}
+
+/// The line a splitter moves, given as the number of tracks before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SplitterTrack {
+    Column(usize),
+    Row(usize),
+}
+
+/// `line` is where the line sits, as a share of the tracks' length.
+#[component]
+fn Splitter(
+    track: SplitterTrack,
+    line: f64,
+    rect: Rect<f64, AreaSpace>,
+    on_move: EventHandler<f64>,
+) -> Element {
+    let mut grid = use_context::<Signal<GridData>>();
+    let mut handle = use_signal(|| None as Option<Rc<MountedData>>);
+    use_key_guard(handle, SPLITTER_KEYS, &[]);
+    let mut last_pos = use_signal(|| None as Option<f64>);
+    let position = move |event: &PointerEvent| {
+        let point = event.data.client_coordinates();
+        match track {
+            SplitterTrack::Column(_) => point.x,
+            SplitterTrack::Row(_) => point.y,
+        }
+    };
+
+    let track_styles = match track {
+        SplitterTrack::Column(_) => COLUMN_SPLITTER_STYLES,
+        SplitterTrack::Row(_) => ROW_SPLITTER_STYLES,
+    };
+    let active_styles = match last_pos().is_some() {
+        true => ACTIVE_SPLITTER_STYLES,
+        false => "",
+    };
+    let rect = grid.read().to_container_rect(rect);
+    let style = format!(
+        "{}{}{}left: {}px; top: {}px; width: {}px; height: {}px;",
+        SPLITTER_STYLES,
+        track_styles,
+        active_styles,
+        rect.origin.x,
+        rect.origin.y,
+        rect.width(),
+        rect.height()
+    );
+
+    rsx! {
+        div {
+            style: style,
+            role: "separator",
+            tabindex: 0,
+            aria_orientation: match track {
+                SplitterTrack::Column(_) => "vertical",
+                SplitterTrack::Row(_) => "horizontal",
+            },
+            aria_valuemin: 0,
+            aria_valuemax: 100,
+            aria_valuenow: "{(line * 100.).round()}",
+            onmounted: move |cx| handle.set(Some(cx.data())),
+            onkeydown: move |event| {
+                let step = match (track, event.data.key()) {
+                    (SplitterTrack::Column(_), Key::ArrowLeft)
+                    | (SplitterTrack::Row(_), Key::ArrowUp) => -SPLITTER_KEY_STEP,
+                    (SplitterTrack::Column(_), Key::ArrowRight)
+                    | (SplitterTrack::Row(_), Key::ArrowDown) => SPLITTER_KEY_STEP,
+                    _ => return,
+                };
+                event.stop_propagation();
+                on_move.call(step);
+            },
+            onpointerdown: move |event| {
+                event.stop_propagation();
+                if let Some(handle) = handle.peek().as_ref() {
+                    capture_pointer(handle, event.data.pointer_id());
+                }
+                last_pos.set(Some(position(&event)));
+                grid.write().set_resizing(true);
+            },
+            onpointermove: move |event| {
+                let last = match *last_pos.peek() {
+                    Some(last) => last,
+                    None => return,
+                };
+                event.stop_propagation();
+                let pos = position(&event);
+                last_pos.set(Some(pos));
+                // pointer travel is in client pixels, tracks are sized in area pixels
+                on_move.call((pos - last) / grid.peek().get_scale());
+            },
+            onpointerup: move |event| {
+                if last_pos.peek().is_some() {
+                    event.stop_propagation();
+                    last_pos.set(None);
+                    grid.write().set_resizing(false);
+                }
+            },
+            onpointercancel: move |_| {
+                last_pos.set(None);
+                grid.write().set_resizing(false);
+            },
+        }
+    }
+}
@@ -1,56 +1,415 @@
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+use super::{get_named_areas, DragError, DragErrorType, GridPlacement, GridSizes, GridTracks};
 use dioxus::prelude::*;
-use dioxus_elements::geometry::euclid::{Point2D, Rect, Size2D};
+use dioxus_elements::geometry::{
+    euclid::{Point2D, Rect, Size2D, Vector2D},
+    ClientSpace,
+};
+
+/// Coordinates relative to a DragArea's top left corner, in CSS pixels, so an
+/// area can sit anywhere in a scrolled or zoomed page. Areas in a
+/// DragCoordinator share the space of its first area.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AreaSpace;
+
+/// The smallest fraction a splitter can shrink a track to.
+const MIN_TRACK_SIZE: f64 = 0.2;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum GridState {
     Initial,
-    Mounted(Rect<f64, f64>),
+    Mounted(Rect<f64, ClientSpace>),
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ColumnData {
     column_width: f64,
-    column_origin: Point2D<f64, f64>,
+    column_origin: Point2D<f64, AreaSpace>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct GridData {
     columns: u8,
-    rows: u8,
+    span_columns: u8,
+    rows: Option<u8>,
+    row_size: Option<String>,
+    areas: Vec<String>,
+    named_areas: BTreeMap<String, GridTracks>,
+    gap: f64,
+    padding: f64,
     state: GridState,
     prev_state: GridState,
+    scale: f64,
+    frame_offset: Vector2D<f64, AreaSpace>,
+    sizes: GridSizes,
+    resizing: bool,
+    cell_count: usize,
 }
 
 impl GridData {
-    pub fn new(columns: u8, rows: u8) -> Self {
+    pub fn new(columns: u8, rows: Option<u8>) -> Self {
         Self {
             columns,
+            span_columns: columns,
             rows,
+            row_size: None,
+            areas: Vec::new(),
+            named_areas: BTreeMap::new(),
+            gap: 0.,
+            padding: 0.,
             state: GridState::Initial,
             prev_state: GridState::Initial,
+            scale: 1.,
+            frame_offset: Vector2D::zero(),
+            sizes: GridSizes {
+                columns: vec![1.; columns as usize],
+                ..Default::default()
+            },
+            resizing: false,
+            cell_count: 0,
+        }
+    }
+
+    pub fn get_columns(&self) -> u8 {
+        self.columns
+    }
+
+    /// Keeps the column sizes of the count it leaves for when the grid returns to it.
+    pub fn set_columns(&mut self, columns: u8) {
+        let columns = columns.max(1);
+        if columns == self.columns {
+            return;
+        }
+        let sizes = self
+            .sizes
+            .breakpoint_columns
+            .remove(&columns)
+            .unwrap_or_else(|| vec![1.; columns as usize]);
+        let previous = std::mem::replace(&mut self.sizes.columns, sizes);
+        self.sizes.breakpoint_columns.insert(self.columns, previous);
+        self.columns = columns;
+    }
+
+    /// The columns Cell spans and named areas refer to. A breakpoint's column
+    /// count stays until it switches.
+    pub fn set_declared_columns(&mut self, columns: u8) {
+        let columns = columns.max(1);
+        if self.columns == self.span_columns {
+            self.set_columns(columns);
+        }
+        self.span_columns = columns;
+    }
+
+    pub fn get_rows(&self) -> Option<u8> {
+        self.rows
+    }
+
+    pub fn set_rows(&mut self, rows: Option<u8>) {
+        self.rows = rows;
+    }
+
+    pub fn set_row_size(&mut self, row_size: Option<String>) {
+        self.row_size = row_size;
+    }
+
+    pub fn set_spacing(&mut self, gap: f64, padding: f64) {
+        self.gap = gap.max(0.);
+        self.padding = padding.max(0.);
+    }
+
+    pub fn get_gap(&self) -> f64 {
+        self.gap
+    }
+
+    /// Areas that are not rectangles leave the grid without named areas.
+    pub fn set_areas(&mut self, areas: Vec<String>) -> Result<(), DragError> {
+        match get_named_areas(&areas) {
+            Ok(named_areas) => {
+                self.named_areas = named_areas;
+                self.areas = areas;
+                Ok(())
+            }
+            Err(name) => {
+                self.named_areas.clear();
+                self.areas.clear();
+                Err(DragError(DragErrorType::InvalidGridArea(name)))
+            }
+        }
+    }
+
+    fn shows_areas(&self) -> bool {
+        !self.areas.is_empty() && self.columns == self.span_columns
+    }
+
+    pub fn resolve_placement(&self, placement: &GridPlacement) -> Option<GridTracks> {
+        match placement {
+            GridPlacement::Lines { .. } => placement.get_tracks(),
+            GridPlacement::Area(name) => self.named_areas.get(name).copied(),
+        }
+    }
+
+    pub fn check_placement(&self, placement: &GridPlacement) -> Result<(), DragError> {
+        let tracks = match (self.resolve_placement(placement), placement) {
+            (Some(tracks), _) => tracks,
+            (None, GridPlacement::Area(name)) => {
+                return Err(DragError(DragErrorType::UnknownGridArea(name.clone())))
+            }
+            (None, GridPlacement::Lines { .. }) => return Ok(()),
+        };
+        let rows_fit = match (tracks.row, self.rows) {
+            (Some(row), Some(rows)) => row >= 1 && row + tracks.row_span <= rows as u64 + 1,
+            (Some(row), None) => row >= 1,
+            (None, Some(rows)) => tracks.row_span <= rows as u64,
+            (None, None) => true,
+        };
+        if tracks.column < 1
+            || tracks.column_span < 1
+            || tracks.row_span < 1
+            || tracks.get_column_end() > self.span_columns as u64 + 1
+            || !rows_fit
+        {
+            return Err(DragError(DragErrorType::PlacementOutOfGrid(
+                placement.clone(),
+            )));
+        }
+        Ok(())
+    }
+
+    /// The CSS placing a Cell, and the column line its trailing splitter sits
+    /// on. Cells that do not fit a narrower breakpoint flow onto the next free
+    /// row, past the last line so they get no splitter.
+    pub fn get_cell_placement(&self, placement: &GridPlacement) -> (String, u64) {
+        let columns = self.columns as u64;
+        let tracks = match self.resolve_placement(placement) {
+            Some(tracks) => tracks,
+            None => return (placement.get_style(), columns + 1),
+        };
+        let pinned = match placement {
+            GridPlacement::Area(_) => self.shows_areas(),
+            GridPlacement::Lines { .. } => tracks.get_column_end() <= columns + 1,
+        };
+        match (pinned, placement) {
+            (true, GridPlacement::Area(name)) => {
+                (format!("grid-area: {};", name), tracks.get_column_end())
+            }
+            (true, _) => (tracks.get_style(), tracks.get_column_end()),
+            (false, _) => (
+                format!(
+                    "grid-column: auto/span {}; grid-row: span {};",
+                    tracks.column_span.min(columns),
+                    tracks.row_span
+                ),
+                columns + 1,
+            ),
+        }
+    }
+
+    /// Without declared rows, the row size applies to the rows the Cells create.
+    pub fn get_grid_style(&self) -> String {
+        let mut style = format!("grid-template-columns: {};", self.get_column_template());
+        match (self.rows, &self.row_size) {
+            (Some(rows), Some(size)) => {
+                style.push_str(&format!(" grid-template-rows: repeat({}, {});", rows, size))
+            }
+            (Some(rows), None) => style.push_str(&format!(
+                " grid-template-rows: repeat({}, minmax(0, 1fr));",
+                rows
+            )),
+            (None, Some(size)) => style.push_str(&format!(" grid-auto-rows: {};", size)),
+            (None, None) => (),
+        }
+        if self.gap > 0. || self.padding > 0. {
+            style.push_str(&format!(
+                " gap: {}px; padding: {}px; box-sizing: border-box;",
+                self.gap, self.padding
+            ));
+        }
+        if self.shows_areas() {
+            let areas: Vec<String> = self.areas.iter().map(|row| format!("{:?}", row)).collect();
+            style.push_str(&format!(" grid-template-areas: {};", areas.join(" ")));
+        }
+        style
+    }
+
+    pub fn get_sizes(&self) -> &GridSizes {
+        &self.sizes
+    }
+
+    /// Restores saved track sizes. Column sizes saved at another column
+    /// count are kept for when a breakpoint switches to it.
+    pub fn set_sizes(&mut self, mut sizes: GridSizes) {
+        if sizes.columns.len() != self.columns as usize {
+            let columns = sizes
+                .breakpoint_columns
+                .remove(&self.columns)
+                .unwrap_or_else(|| vec![1.; self.columns as usize]);
+            let saved = std::mem::replace(&mut sizes.columns, columns);
+            if !saved.is_empty() {
+                sizes.breakpoint_columns.insert(saved.len() as u8, saved);
+            }
         }
+        self.sizes = sizes;
     }
 
-    pub fn update_mounted(&mut self, rect: Rect<f64, f64>) {
+    /// Sizes are recorded once the splitter being dragged is released.
+    pub fn is_resizing(&self) -> bool {
+        self.resizing
+    }
+
+    pub fn set_resizing(&mut self, resizing: bool) {
+        self.resizing = resizing;
+    }
+
+    pub fn claim_cell_id(&mut self, requested: Option<String>) -> String {
+        self.cell_count += 1;
+        requested.unwrap_or_else(|| format!("cell-{}", self.cell_count))
+    }
+
+    pub fn get_column_template(&self) -> String {
+        get_track_template(&self.sizes.columns)
+    }
+
+    pub fn get_row_template(&self, cell_id: &str, rows: u64) -> String {
+        match self.sizes.rows.get(cell_id) {
+            Some(sizes) if sizes.len() == rows as usize => get_track_template(sizes),
+            _ => format!("repeat({}, minmax(0, 1fr))", rows),
+        }
+    }
+
+    pub fn get_column_lines(&self) -> Vec<f64> {
+        get_track_lines(&self.sizes.columns)
+    }
+
+    /// Each line between a Cell's rows, as a share of the Cell's height.
+    pub fn get_row_lines(&self, cell_id: &str, rows: u64) -> Vec<f64> {
+        match self.sizes.rows.get(cell_id) {
+            Some(sizes) if sizes.len() == rows as usize => get_track_lines(sizes),
+            _ => get_track_lines(&vec![1.; rows as usize]),
+        }
+    }
+
+    pub fn move_column_line(&mut self, boundary: usize, delta: f64) {
+        // only the columns themselves grow and shrink, not the gaps and padding
+        let spacing = self.gap * (self.columns as f64 - 1.) + self.padding * 2.;
+        let width = match self.get_area_rect() {
+            Some(rect) => rect.width() - spacing,
+            None => return,
+        };
+        move_track_line(&mut self.sizes.columns, boundary, delta, width);
+    }
+
+    /// `height` is the combined height of the Cell's rows.
+    pub fn move_row_line(
+        &mut self,
+        cell_id: &str,
+        rows: u64,
+        boundary: usize,
+        delta: f64,
+        height: f64,
+    ) {
+        let sizes = self
+            .sizes
+            .rows
+            .entry(cell_id.to_string())
+            .or_insert_with(|| vec![1.; rows as usize]);
+        if sizes.len() != rows as usize {
+            *sizes = vec![1.; rows as usize];
+        }
+        move_track_line(sizes, boundary, delta, height);
+    }
+
+    /// `layout_width` differs from the client rect's width when the area is
+    /// zoomed or scaled.
+    pub fn update_mounted(&mut self, rect: Rect<f64, ClientSpace>, layout_width: Option<f64>) {
         self.prev_state = self.state.clone();
         self.state = GridState::Mounted(rect);
+        self.scale = match layout_width {
+            Some(layout_width) if layout_width > 0. => rect.width() / layout_width,
+            _ => 1.,
+        };
     }
 
-    pub fn get_grid_rect(&self) -> Option<Rect<f64, f64>> {
+    pub fn get_grid_rect(&self) -> Option<Rect<f64, ClientSpace>> {
         match self.state {
             GridState::Mounted(rect) => Some(rect),
             GridState::Initial => None,
         }
     }
 
-    pub fn get_new_child_rect(&self, grid_child_rect: Rect<f64, f64>) -> Rect<f64, f64> {
+    /// Client pixels per area pixel.
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Zero for an area outside a DragCoordinator.
+    pub fn set_frame_offset(&mut self, offset: Vector2D<f64, AreaSpace>) {
+        self.frame_offset = offset;
+    }
+
+    pub fn get_frame_offset(&self) -> Vector2D<f64, AreaSpace> {
+        self.frame_offset
+    }
+
+    /// Panels, previews and splitters are positioned against the Container.
+    pub fn to_container_rect(&self, rect: Rect<f64, AreaSpace>) -> Rect<f64, AreaSpace> {
+        rect.translate(-self.frame_offset)
+    }
+
+    pub fn from_container_rect(&self, rect: Rect<f64, AreaSpace>) -> Rect<f64, AreaSpace> {
+        rect.translate(self.frame_offset)
+    }
+
+    pub fn get_area_rect(&self) -> Option<Rect<f64, AreaSpace>> {
+        self.get_grid_rect().map(|rect| self.to_area_rect(rect))
+    }
+
+    pub fn to_area_point(&self, point: Point2D<f64, ClientSpace>) -> Point2D<f64, AreaSpace> {
+        let origin = self
+            .get_grid_rect()
+            .map_or(Point2D::origin(), |rect| rect.origin);
+        self.to_area_point_at(point, origin)
+    }
+
+    pub fn to_area_rect(&self, rect: Rect<f64, ClientSpace>) -> Rect<f64, AreaSpace> {
+        let origin = self
+            .get_grid_rect()
+            .map_or(Point2D::origin(), |rect| rect.origin);
+        self.to_area_rect_at(rect, origin)
+    }
+
+    /// Converts against where the area sat when it was read.
+    pub fn to_area_point_at(
+        &self,
+        point: Point2D<f64, ClientSpace>,
+        area_origin: Point2D<f64, ClientSpace>,
+    ) -> Point2D<f64, AreaSpace> {
+        ((point - area_origin) / self.scale).cast_unit().to_point() + self.frame_offset
+    }
+
+    pub fn to_area_rect_at(
+        &self,
+        rect: Rect<f64, ClientSpace>,
+        area_origin: Point2D<f64, ClientSpace>,
+    ) -> Rect<f64, AreaSpace> {
+        Rect::new(
+            self.to_area_point_at(rect.origin, area_origin),
+            (rect.size / self.scale).cast_unit(),
+        )
+    }
+
+    pub fn get_new_child_rect(
+        &self,
+        grid_child_rect: Rect<f64, AreaSpace>,
+    ) -> Rect<f64, AreaSpace> {
         match (&self.state, &self.prev_state) {
             (GridState::Mounted(new_grid_rect), GridState::Mounted(old_grid_rect)) => {
                 let prop_x = grid_child_rect.width() / old_grid_rect.width();
                 let prop_y = grid_child_rect.height() / old_grid_rect.height();
                 let new_width = new_grid_rect.width() * prop_x;
                 let new_height = new_grid_rect.height() * prop_y;
-                let new_size: Size2D<f64, f64> = Size2D::new(new_width, new_height);
+                let new_size: Size2D<f64, AreaSpace> = Size2D::new(new_width, new_height);
                 tracing::info!(
                     "old size {:?} new size {:?}",
                     grid_child_rect.size,
@@ -59,7 +418,7 @@ impl GridData {
 
                 let new_x = grid_child_rect.origin.x;
                 let new_y = grid_child_rect.origin.y;
-                let new_origin: Point2D<f64, f64> = Point2D::new(new_x, new_y);
+                let new_origin: Point2D<f64, AreaSpace> = Point2D::new(new_x, new_y);
                 Rect::new(new_origin, new_size)
             }
             (_, _) => grid_child_rect,
@@ -67,14 +426,83 @@ impl GridData {
     }
 }
 
+/// The root element of a DragArea, which its Cells and DragTargets are measured against.
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) struct AreaElement(pub Signal<Option<Rc<MountedData>>>);
+
+/// Reads the area alongside the element, so a page scrolled since the area
+/// was last read cannot skew the result.
+pub(crate) async fn measure_in_area(
+    element: Rc<MountedData>,
+    area: AreaElement,
+    grid: Signal<GridData>,
+) -> Option<Rect<f64, AreaSpace>> {
+    let area_element = area.0.peek().clone()?;
+    let rect = element.get_client_rect().await.ok()?;
+    let area_rect = area_element.get_client_rect().await.ok()?;
+    let area_origin = area_rect.origin.cast_unit();
+    Some(grid.peek().to_area_rect_at(rect.cast_unit(), area_origin))
+}
+
+/// The width before any zoom or transform scales it.
+pub(crate) fn get_layout_width(element: &MountedData) -> Option<f64> {
+    #[cfg(target_arch = "wasm32")]
+    {
+        use wasm_bindgen::JsCast;
+        element
+            .downcast::<web_sys::Element>()
+            .and_then(|element| element.dyn_ref::<web_sys::HtmlElement>())
+            .map(|element| element.offset_width() as f64)
+    }
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let _ = element;
+        None
+    }
+}
+
+fn get_track_template(sizes: &[f64]) -> String {
+    sizes
+        .iter()
+        .map(|size| format!("minmax(0, {}fr)", size))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn get_track_lines(sizes: &[f64]) -> Vec<f64> {
+    let total: f64 = sizes.iter().sum();
+    sizes
+        .iter()
+        .scan(0., |position, size| {
+            *position += size;
+            Some(*position / total)
+        })
+        .take(sizes.len().saturating_sub(1))
+        .collect()
+}
+
+/// Keeps both tracks above MIN_TRACK_SIZE.
+fn move_track_line(sizes: &mut [f64], boundary: usize, delta: f64, length: f64) {
+    if boundary == 0 || boundary >= sizes.len() || length <= 0. {
+        return;
+    }
+    let total: f64 = sizes.iter().sum();
+    let (before, after) = (sizes[boundary - 1], sizes[boundary]);
+    let delta = (delta * total / length)
+        .max(MIN_TRACK_SIZE - before)
+        .min(after - MIN_TRACK_SIZE);
+    sizes[boundary - 1] = before + delta;
+    sizes[boundary] = after - delta;
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SnapInfo {
-    pub rect: Rect<f64, f64>,
+    pub rect: Rect<f64, AreaSpace>,
     pub target_id: Option<String>,
 }
 
 impl SnapInfo {
-    pub fn new(target_id: Option<String>, rect: Rect<f64, f64>) -> Self {
+    pub fn new(target_id: Option<String>, rect: Rect<f64, AreaSpace>) -> Self {
         Self { rect, target_id }
     }
 }
@@ -82,7 +510,7 @@ impl SnapInfo {
 #[derive(Clone, PartialEq, Debug)]
 pub enum DragOrigin {
     Snapped(SnapInfo),
-    Free(Rect<f64, f64>),
+    Free(Rect<f64, AreaSpace>),
 }
 
 impl DragOrigin {
@@ -93,3 +521,158 @@ impl DragOrigin {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn areas(rows: &[&str]) -> Vec<String> {
+        rows.iter().map(|row| row.to_string()).collect()
+    }
+
+    #[test]
+    fn placements_inside_the_grid_pass() {
+        let grid = GridData::new(6, Some(2));
+        assert!(grid.check_placement(&GridPlacement::columns(1, 6)).is_ok());
+        assert!(grid
+            .check_placement(&GridPlacement::columns(4, 3).with_rows(2, 1))
+            .is_ok());
+    }
+
+    #[test]
+    fn placements_outside_the_grid_fail() {
+        let grid = GridData::new(6, Some(2));
+        for placement in [
+            GridPlacement::columns(0, 0),
+            GridPlacement::columns(5, 3),
+            GridPlacement::columns(1, 1).with_rows(2, 2),
+            GridPlacement::columns(1, 1).with_rows(0, 1),
+        ] {
+            assert_eq!(
+                grid.check_placement(&placement),
+                Err(DragError(DragErrorType::PlacementOutOfGrid(placement)))
+            );
+        }
+    }
+
+    #[test]
+    fn placements_name_known_areas() {
+        let mut grid = GridData::new(2, None);
+        grid.set_areas(areas(&["head head", "side main"])).unwrap();
+        assert!(grid
+            .check_placement(&GridPlacement::area("main".into()))
+            .is_ok());
+        assert_eq!(
+            grid.check_placement(&GridPlacement::area("foot".into())),
+            Err(DragError(DragErrorType::UnknownGridArea("foot".into())))
+        );
+    }
+
+    #[test]
+    fn invalid_areas_leave_the_grid_without_areas() {
+        let mut grid = GridData::new(2, None);
+        assert_eq!(
+            grid.set_areas(areas(&["a a", "a b"])),
+            Err(DragError(DragErrorType::InvalidGridArea("a".into())))
+        );
+        assert!(grid
+            .resolve_placement(&GridPlacement::area("b".into()))
+            .is_none());
+    }
+
+    #[test]
+    fn track_lines_are_shares_of_the_total() {
+        assert_eq!(get_track_lines(&[1., 1., 2.]), vec![0.25, 0.5]);
+        assert_eq!(get_track_lines(&[1.]), Vec::<f64>::new());
+        assert_eq!(get_track_lines(&[]), Vec::<f64>::new());
+    }
+
+    #[test]
+    fn moving_a_track_line_trades_size_between_neighbours() {
+        let mut sizes = vec![1., 1., 1.];
+        // 3 fr over 300px, so 50px is half a track
+        move_track_line(&mut sizes, 1, 50., 300.);
+        assert_eq!(sizes, vec![1.5, 0.5, 1.]);
+        move_track_line(&mut sizes, 2, -100., 300.);
+        assert_eq!(sizes, vec![1.5, 0.2, 1.3]);
+    }
+
+    #[test]
+    fn track_lines_stop_at_the_minimum_track_size() {
+        let close = |sizes: &[f64], expected: [f64; 2]| {
+            sizes
+                .iter()
+                .zip(expected)
+                .all(|(size, expected)| (size - expected).abs() < 1e-9)
+        };
+        let mut sizes = vec![1., 1.];
+        move_track_line(&mut sizes, 1, 1000., 200.);
+        assert!(close(&sizes, [1.8, MIN_TRACK_SIZE]));
+        move_track_line(&mut sizes, 1, -1000., 200.);
+        assert!(close(&sizes, [MIN_TRACK_SIZE, 1.8]));
+    }
+
+    #[test]
+    fn outer_lines_and_empty_lengths_do_not_move() {
+        let mut sizes = vec![1., 1.];
+        move_track_line(&mut sizes, 0, 50., 200.);
+        move_track_line(&mut sizes, 2, 50., 200.);
+        move_track_line(&mut sizes, 1, 50., 0.);
+        assert_eq!(sizes, vec![1., 1.]);
+    }
+
+    #[test]
+    fn column_sizes_are_kept_per_breakpoint() {
+        let mut grid = GridData::new(3, None);
+        grid.set_sizes(GridSizes {
+            columns: vec![2., 1.],
+            ..Default::default()
+        });
+        assert_eq!(grid.get_sizes().columns, vec![1., 1., 1.]);
+        grid.set_columns(2);
+        assert_eq!(grid.get_sizes().columns, vec![2., 1.]);
+        assert_eq!(
+            grid.get_sizes().breakpoint_columns.get(&3),
+            Some(&vec![1., 1., 1.])
+        );
+    }
+
+    #[test]
+    fn cells_are_placed_on_their_lines() {
+        let grid = GridData::new(6, None);
+        assert_eq!(
+            grid.get_cell_placement(&GridPlacement::columns(2, 3)),
+            ("grid-column: 2/span 3; grid-row: span 1;".to_string(), 5)
+        );
+    }
+
+    #[test]
+    fn cells_past_a_narrow_breakpoint_flow_without_a_splitter() {
+        let mut grid = GridData::new(6, None);
+        grid.set_columns(3);
+        assert_eq!(
+            grid.get_cell_placement(&GridPlacement::columns(3, 2)),
+            ("grid-column: auto/span 2; grid-row: span 1;".to_string(), 4)
+        );
+        assert_eq!(
+            grid.get_cell_placement(&GridPlacement::columns(1, 6)),
+            ("grid-column: auto/span 3; grid-row: span 1;".to_string(), 4)
+        );
+    }
+
+    #[test]
+    fn cells_take_named_areas_while_the_declared_columns_show() {
+        let mut grid = GridData::new(3, None);
+        grid.set_areas(areas(&["a a b"])).unwrap();
+        let placement = GridPlacement::area("a".into());
+        assert_eq!(
+            grid.get_cell_placement(&placement),
+            ("grid-area: a;".to_string(), 3)
+        );
+        grid.set_columns(2);
+        assert_eq!(
+            grid.get_cell_placement(&placement),
+            ("grid-column: auto/span 2; grid-row: span 1;".to_string(), 3)
+        );
+    }
+}
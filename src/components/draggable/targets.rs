@@ -2,11 +2,20 @@ use std::rc::Rc;
 
 use crate::components::draggable::*;
 use dioxus::prelude::*;
-use dioxus_elements::geometry::euclid::Rect;
+use dioxus_elements::geometry::euclid::{Rect, Size2D};
 
 const DRAG_TARGET_STYLE: &str = "
     width: 100%;
     height: 100%;
+    flex: 1 1 0;
+    min-width: 0;
+    min-height: 0;
+";
+
+const SPLIT_DRAG_TARGET_STYLE: &str = "
+    display: flex;
+    width: 100%;
+    height: 100%;
 ";
 
 const DRAG_TARGET_ACTIVE_STYLE: &str = "
@@ -14,58 +23,206 @@ const DRAG_TARGET_ACTIVE_STYLE: &str = "
     background-image: repeating-linear-gradient(50deg, var(--fg), var(--fg) .05rem, transparent .01rem, transparent .4rem);
 ";
 
+const DRAG_TARGET_REJECTING_STYLE: &str = "
+    cursor: no-drop;
+    opacity: .5;
+";
+
+const DROP_PREVIEW_STYLE: &str = "
+    position: absolute;
+    z-index: 6000;
+    pointer-events: none;
+    background-color: var(--accent_0);
+    opacity: .5;
+";
+
+/// A drag handle's 2rem height, at the default 16px root font size.
+pub(crate) const TAB_STRIP_HEIGHT: f64 = 32.;
+
+const EDGE_ZONE_FRACTION: f64 = 0.25;
+
 #[component]
-pub fn DragTarget(children: Element) -> Element {
-    let id = use_signal(|| uuid::Uuid::new_v4().to_string());
+pub fn DragTarget(
+    id: Option<String>,
+    label: Option<String>,
+    accepts: Option<DropFilter>,
+    capacity: Option<usize>,
+    #[props(default)] tabs: bool,
+    #[props(default)] splittable: bool,
+    inset: Option<f64>,
+    children: Element,
+) -> Element {
+    let mut layout_state = use_context::<Signal<LayoutState>>();
+    let mut ids = use_context::<CopyValue<IdRegistry>>();
+    let (claimed_id, id_error) = use_hook(|| {
+        let split_target = id
+            .as_ref()
+            .is_some_and(|id| layout_state.peek().is_split_target(id));
+        ids.write().claim_target_id(id.clone(), split_target)
+    });
+    let id = use_signal(|| claimed_id);
     let mut global_drag_state = use_context::<Signal<GlobalDragState>>();
+    let config = use_context::<DragAreaConfig>();
+    let grid = use_context::<Signal<GridData>>();
+    use_effect(move || {
+        let mut layout = layout_state.write();
+        if let Some(error) = id_error.clone() {
+            layout.report_error(error);
+        }
+        layout.record_target_area(id.peek().clone(), config.area_id.clone());
+    });
+    use_effect(use_reactive(&label, move |label| {
+        layout_state
+            .write()
+            .record_target_label(id.peek().clone(), label);
+    }));
+    // rules follow the props, so a target can start or stop taking panels
+    use_effect(use_reactive(
+        (&accepts, &capacity, &tabs),
+        move |(accepts, capacity, tabs)| {
+            layout_state.write().record_target_rules(
+                id.peek().clone(),
+                TargetRules {
+                    accepts,
+                    capacity,
+                    tabs,
+                },
+            );
+        },
+    ));
     let mut target_div = use_signal(|| None as Option<Rc<MountedData>>);
-    let mut target_rect = use_signal(|| None as Option<Rect<f64, f64>>);
+    let mut target_rect = use_signal(|| None as Option<Rect<f64, AreaSpace>>);
 
     let mut initial_snap_info = use_context_provider(|| Signal::new(None as Option<SnapInfo>));
 
+    let area_element = use_context::<AreaElement>();
     let read_target_rect = move || async move {
         tracing::info!("reading target rect");
-        let read = target_div.peek();
-        let client_rect = read.as_ref().map(|el| el.get_client_rect());
-
-        if let Some(client_rect) = client_rect {
-            if let Ok(rect) = client_rect.await {
+        let element = target_div.peek().clone();
+        if let Some(element) = element {
+            if let Some(rect) = measure_in_area(element, area_element, grid).await {
                 tracing::info!("setting target rect");
-                target_rect.set(Some(rect.cast_unit()));
+                target_rect.set(Some(rect));
             }
         }
     };
 
+    // splits, splitters and window resizes change the target's size, so it is measured again
+    let split = use_memo(move || layout_state.read().get_split(&id.peek()));
+    let grid_sizes = use_memo(move || grid.read().get_sizes().clone());
+    let area_rect = use_memo(move || grid.read().get_area_rect());
+    let target_resizes = use_resize_count(target_div);
     use_effect(move || {
+        let _area = area_rect.read();
         let _trig = target_div.read();
+        let _split = split.read();
+        let _sizes = grid_sizes.read();
+        let _resizes = target_resizes.read();
         spawn(async move {
             read_target_rect().await;
         });
     });
 
+    // panels snap to the target's rect shrunk by its inset, leaving a gutter around them
+    let snap_rect = use_memo(move || {
+        let rect = target_rect()?;
+        let inset = inset
+            .unwrap_or_default()
+            .max(0.)
+            .min(rect.width() / 2.)
+            .min(rect.height() / 2.);
+        Some(rect.inflate(-inset, -inset))
+    });
+
     use_effect(move || {
-        if let Some(rect) = target_rect() {
+        if let Some(rect) = snap_rect() {
             initial_snap_info.set(Some(SnapInfo::new(Some(id.peek().clone()), rect)));
+            layout_state
+                .write()
+                .register_target(id.peek().clone(), rect);
         }
     });
 
-    let target_is_active = use_memo(move || {
+    let dropped_id = id.peek().clone();
+    use_drop(move || {
+        if let Ok(mut layout) = layout_state.try_write() {
+            layout.remove_target(&dropped_id);
+        }
+        if let Ok(mut ids) = ids.try_write() {
+            ids.release_target_id(&dropped_id);
+        }
+    });
+
+    // the verdict for the drag under the pointer, None while the pointer is elsewhere.
+    // a tab stack, or the tab strip of a tabs target, takes the panel as a tab.
+    // the edges of an occupied splittable target split it
+    let hover = use_memo(move || {
         let drag_state = global_drag_state.read().get_drag_state();
-        let target_rect = match target_rect.peek().clone() {
-            Some(rect) => rect,
-            None => return false,
-        };
+        let target_rect = target_rect.peek().clone()?;
         match drag_state {
-            DragAreaStates::Dragging(drag_info) => {
-                target_rect.contains(drag_info.current_pos.cast_unit())
+            DragAreaStates::Dragging(drag_info) if target_rect.contains(drag_info.current_pos) => {
+                let layout = layout_state.peek();
+                let on_tab_strip =
+                    drag_info.current_pos.y < target_rect.origin.y + TAB_STRIP_HEIGHT;
+                let occupied = layout
+                    .get_panels_in_target(&id.peek())
+                    .iter()
+                    .any(|panel_id| *panel_id != drag_info.panel_id);
+                let edge = match splittable && occupied && split.peek().is_none() {
+                    true => {
+                        Edge::from_point(target_rect, drag_info.current_pos, EDGE_ZONE_FRACTION)
+                    }
+                    false => None,
+                };
+                let mode = match edge {
+                    _ if tabs && on_tab_strip => DropMode::Stack,
+                    Some(edge) => DropMode::Split(edge),
+                    None if layout.is_tab_stack(&id.peek()) => DropMode::Stack,
+                    None => DropMode::Swap,
+                };
+                let verdict = layout.check_drop(
+                    &drag_info.panel_id,
+                    &drag_info.starting_data,
+                    &id.peek(),
+                    mode,
+                );
+                Some((mode, verdict))
             }
-            _ => false,
+            _ => None,
+        }
+    });
+    let target_is_active = use_memo(move || matches!(hover(), Some((_, Ok(())))));
+    let target_is_rejecting = use_memo(move || matches!(hover(), Some((_, Err(_)))));
+    // where the panel would land when dropped as a tab or into a split
+    let preview_rect = use_memo(move || {
+        let rect = snap_rect.peek().clone()?;
+        match hover()? {
+            (DropMode::Stack, Ok(())) => Some(Rect::new(
+                rect.origin,
+                Size2D::new(rect.width(), TAB_STRIP_HEIGHT.min(rect.height())),
+            )),
+            (DropMode::Split(edge), Ok(())) => Some(edge.split_rect(rect)),
+            _ => None,
+        }
+    });
+
+    use_effect(move || {
+        let rejection = match hover() {
+            Some((_, Err(rejection))) => Some(rejection),
+            _ => None,
+        };
+        let owns_rejection = global_drag_state
+            .peek()
+            .get_rejection()
+            .is_some_and(|current| current.target_id == *id.peek());
+        if rejection.is_some() || owns_rejection {
+            global_drag_state.write().set_rejection(rejection);
         }
     });
 
     use_effect(move || {
         let active = target_is_active();
-        let rect = match target_rect.peek().clone() {
+        let rect = match snap_rect.peek().clone() {
             Some(rect) => rect,
             None => return,
         };
@@ -73,6 +230,13 @@ pub fn DragTarget(children: Element) -> Element {
             let snap_info = SnapInfo::new(Some(id.peek().clone()), rect);
             global_drag_state.write().set_snap_info(Some(snap_info))
         } else {
+            let owns_snap = global_drag_state
+                .peek()
+                .get_snap_info()
+                .is_some_and(|info| info.rect == rect);
+            if owns_snap && global_drag_state.peek().get_drop_mode() != DropMode::Swap {
+                global_drag_state.write().set_drop_mode(DropMode::Swap);
+            }
             let drag_state = global_drag_state.peek().get_drag_state();
             match drag_state {
                 DragAreaStates::Dragging(_drag_info) => {
@@ -88,18 +252,93 @@ pub fn DragTarget(children: Element) -> Element {
         }
     });
 
-    let style = use_memo(move || match target_is_active() {
-        true => format!("{}{}", DRAG_TARGET_STYLE, DRAG_TARGET_ACTIVE_STYLE),
-        false => DRAG_TARGET_STYLE.to_string(),
+    use_effect(move || {
+        let mode = match hover() {
+            Some((mode, Ok(()))) => mode,
+            _ => return,
+        };
+        if global_drag_state.peek().get_drop_mode() != mode {
+            global_drag_state.write().set_drop_mode(mode);
+        }
     });
 
+    let aria_label = use_memo(move || layout_state.read().get_target_label(&id.peek()));
+    let drop_effect = use_memo(move || match global_drag_state.read().get_drag_state() {
+        DragAreaStates::Dragging(drag_info)
+            if layout_state.peek().accepts_drop(
+                &drag_info.panel_id,
+                &drag_info.starting_data,
+                &id.peek(),
+                match layout_state.peek().is_tab_stack(&id.peek()) {
+                    true => DropMode::Stack,
+                    false => DropMode::Swap,
+                },
+            ) =>
+        {
+            "move"
+        }
+        _ => "none",
+    });
+
+    let style = use_memo(move || match (target_is_active(), target_is_rejecting()) {
+        (true, _) => format!("{}{}", DRAG_TARGET_STYLE, DRAG_TARGET_ACTIVE_STYLE),
+        (false, true) => format!("{}{}", DRAG_TARGET_STYLE, DRAG_TARGET_REJECTING_STYLE),
+        (false, false) => DRAG_TARGET_STYLE.to_string(),
+    });
+
+    let preview_style =
+        use_memo(
+            move || match preview_rect().map(|rect| grid.read().to_container_rect(rect)) {
+                Some(rect) => format!(
+                    "{}left: {}px; top: {}px; width: {}px; height: {}px;",
+                    DROP_PREVIEW_STYLE,
+                    rect.origin.x,
+                    rect.origin.y,
+                    rect.width(),
+                    rect.height()
+                ),
+                None => "display: none;".to_string(),
+            },
+        );
+    let split_style = use_memo(move || match split() {
+        Some(split) if split.edge.is_horizontal() => {
+            format!("{}flex-direction: row;", SPLIT_DRAG_TARGET_STYLE)
+        }
+        Some(_) => format!("{}flex-direction: column;", SPLIT_DRAG_TARGET_STYLE),
+        None => "display: contents;".to_string(),
+    });
+    // the target split off this one, rendered on its side of the split
+    let split_target = move |leading: bool| match split() {
+        Some(split) if split.edge.is_leading() == leading => rsx! {
+            DragTarget {
+                key: "{split.target_id}",
+                id: split.target_id.clone(),
+                accepts: accepts.clone(),
+                capacity: capacity,
+                tabs: tabs,
+                splittable: true,
+                inset: inset,
+            }
+        },
+        _ => None,
+    };
+
     rsx! {
         div {
-            id: id,
-            key: "{id}",
-            style: style,
-            onmounted: move |cx| target_div.set(Some(cx.data())),
-            {children}
+            style: split_style,
+            {split_target(true)},
+            div {
+                id: id,
+                key: "{id}",
+                style: style,
+                role: "group",
+                aria_label: aria_label,
+                aria_dropeffect: drop_effect,
+                onmounted: move |cx| target_div.set(Some(cx.data())),
+                div { style: preview_style }
+                {children}
+            }
+            {split_target(false)}
         }
     }
 }
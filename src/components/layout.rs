@@ -1,3 +1,6 @@
+use std::rc::Rc;
+
+use crate::components::draggable::{CellSplitters, GridData, GridPlacement, LayoutState};
 use dioxus::prelude::*;
 
 const CONTAINER_STYLE: &str = "
@@ -13,7 +16,13 @@ const CONTAINER_STYLE: &str = "
 ";
 
 #[component]
-pub fn Container(columns: Option<u64>, rows: Option<u64>, children: Element) -> Element {
+pub fn Container(
+    columns: Option<u64>,
+    rows: Option<u64>,
+    style: Option<String>,
+    children: Element,
+) -> Element {
+    let user_style = style;
     let mut style = CONTAINER_STYLE.to_string();
 
     if let Some(num) = rows {
@@ -29,6 +38,10 @@ pub fn Container(columns: Option<u64>, rows: Option<u64>, children: Element) ->
             style, num
         );
     }
+
+    if let Some(user_style) = user_style {
+        style = format!("{}\n {}", style, user_style);
+    }
     rsx! {
         div {
             style: style,
@@ -45,23 +58,45 @@ const CELL_STYLE: &str = "
     display: grid;
 ";
 
+/// Give a Cell an `id` to keep its row sizes across reloads.
 #[component]
 pub fn Cell(
-    span: std::ops::Range<u64>,
+    id: Option<String>,
+    placement: GridPlacement,
     rows: Option<u64>,
     columns: Option<u64>,
+    gap: Option<f64>,
+    padding: Option<f64>,
     children: Element,
 ) -> Element {
-    let mut style = format!(
-        "{}\n grid-column: {}/span {};",
-        CELL_STYLE, span.start, span.end
-    );
+    let grid = try_use_context::<Signal<GridData>>();
+    let layout_state = try_use_context::<Signal<LayoutState>>();
+    let cell_id = use_hook(|| {
+        grid.map(|mut grid| {
+            if let Err(error) = grid.peek().check_placement(&placement) {
+                if let Some(mut layout_state) = layout_state {
+                    layout_state.write().report_error(error);
+                }
+            }
+            grid.write().claim_cell_id(id.clone())
+        })
+    });
+    let mut cell = use_signal(|| None as Option<Rc<MountedData>>);
+
+    // a misplaced Cell is reported above and gets no splitters
+    let fits = grid.is_some_and(|grid| grid.read().check_placement(&placement).is_ok());
+    let (placement_style, column_line) = match grid {
+        Some(grid) => grid.read().get_cell_placement(&placement),
+        None => (placement.get_style(), 0),
+    };
+    let mut style = format!("{}\n {}", CELL_STYLE, placement_style);
 
     if let Some(num) = rows {
-        style = format!(
-            "{}\n grid-template-rows: repeat({}, minmax(0, 1fr));",
-            style, num
-        )
+        let template = match (grid, &cell_id) {
+            (Some(grid), Some(cell_id)) => grid.read().get_row_template(cell_id, num),
+            (_, _) => format!("repeat({}, minmax(0, 1fr))", num),
+        };
+        style = format!("{}\n grid-template-rows: {};", style, template)
     }
 
     if let Some(num) = columns {
@@ -71,10 +106,29 @@ pub fn Cell(
         );
     }
 
+    if let Some(gap) = gap {
+        style = format!("{}\n gap: {}px;", style, gap);
+    }
+
+    if let Some(padding) = padding {
+        style = format!("{}\n padding: {}px;", style, padding);
+    }
+
     rsx! {
         div {
             style: style,
+            onmounted: move |cx| cell.set(Some(cx.data())),
             {children}
+            {cell_id.filter(|_| fits).and_then(|cell_id| rsx! {
+                CellSplitters {
+                    cell_id: cell_id,
+                    cell: cell,
+                    column_line: column_line,
+                    rows: rows,
+                    gap: gap.unwrap_or_default(),
+                    padding: padding.unwrap_or_default(),
+                }
+            })}
         }
     }
 }